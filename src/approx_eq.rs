@@ -0,0 +1,211 @@
+//! Approximate equality of points.
+use crate::Point;
+use num::Float;
+use std::ops::Sub;
+
+/// Floating point types that can be compared by the distance in ULPs (units in the last place).
+pub trait Ulps: Float {
+    /// The number of representable values between `self` and `other`, or `None` if one of them
+    /// is NaN. `0.0` and `-0.0` are 0 ULPs apart.
+    fn ulps_distance(self, other: Self) -> Option<u64>;
+}
+
+impl Ulps for f32 {
+    fn ulps_distance(self, other: Self) -> Option<u64> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        // map the bits to integers that are ordered like the floats.
+        let key = |x: f32| {
+            let bits = x.to_bits() as i32;
+            if bits < 0 {
+                i32::MIN as i64 - bits as i64
+            } else {
+                bits as i64
+            }
+        };
+        Some((key(self) - key(other)).unsigned_abs())
+    }
+}
+
+impl Ulps for f64 {
+    fn ulps_distance(self, other: Self) -> Option<u64> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        let key = |x: f64| {
+            let bits = x.to_bits() as i64;
+            if bits < 0 {
+                i64::MIN as i128 - bits as i128
+            } else {
+                bits as i128
+            }
+        };
+        Some(
+            (key(self) - key(other))
+                .unsigned_abs()
+                .min(u64::MAX as u128) as u64,
+        )
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Clone + Copy + PartialOrd + Sub<Output = T>,
+{
+    /// Check if the points are close to each other, in each dimension, up to epsilon. Unlike
+    /// [`Point::close`], the difference is calculated without negative values, so it works for
+    /// unsigned types too.
+    /// # Examples
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<u32> = Point::new_from_vec(&vec![5,10,15]);
+    /// let p2: Point<u32> = Point::new_from_vec(&vec![7,8,14]);
+    /// assert!(p1.close_abs_diff(&p2, 2));
+    /// assert!(!p1.close_abs_diff(&p2, 1));
+    /// ```
+    pub fn close_abs_diff(&self, other: &Self, eps: T) -> bool {
+        self.get_size() == other.get_size()
+            && self
+                .get_vector()
+                .iter()
+                .zip(other.get_vector().iter())
+                .all(|(a, b)| {
+                    let diff = if a > b { *a - *b } else { *b - *a };
+                    diff <= eps
+                })
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Float,
+{
+    /// Check if the points are close to each other, in each dimension, relative to the size of
+    /// the values: two values are close when their difference is at most `eps` (for values near
+    /// zero), or at most `max_relative` times the bigger absolute value.
+    /// # Examples
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1e10, 1e-10]);
+    /// let p2: Point<f64> = Point::new_from_vec(&vec![1.0001e10, 1.0001e-10]);
+    /// assert!(p1.close_relative(&p2, 1e-12, 1e-3));
+    /// assert!(!p1.close_relative(&p2, 1e-12, 1e-6));
+    /// ```
+    pub fn close_relative(&self, other: &Self, eps: T, max_relative: T) -> bool {
+        self.get_size() == other.get_size()
+            && self
+                .get_vector()
+                .iter()
+                .zip(other.get_vector().iter())
+                .all(|(a, b)| relative_eq(*a, *b, eps, max_relative))
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Ulps,
+{
+    /// Check if the points are close to each other, in each dimension, by the number of
+    /// representable floats between the values: two values are close when their difference is
+    /// at most `eps` (for values near zero), or when they are at most `max_ulps` apart.
+    /// # Examples
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![0.1 + 0.2, 1.0]);
+    /// let p2: Point<f64> = Point::new_from_vec(&vec![0.3, 1.0]);
+    /// assert!(p1 != p2);
+    /// assert!(p1.close_ulps(&p2, 0.0, 1));
+    /// ```
+    pub fn close_ulps(&self, other: &Self, eps: T, max_ulps: u64) -> bool {
+        self.get_size() == other.get_size()
+            && self
+                .get_vector()
+                .iter()
+                .zip(other.get_vector().iter())
+                .all(|(a, b)| ulps_eq(*a, *b, eps, max_ulps))
+    }
+}
+
+pub(crate) fn relative_eq<T: Float>(a: T, b: T, eps: T, max_relative: T) -> bool {
+    if a == b {
+        return true;
+    }
+    if a.is_infinite() || b.is_infinite() {
+        return false;
+    }
+    let diff = (a - b).abs();
+    diff <= eps || diff <= a.abs().max(b.abs()) * max_relative
+}
+
+pub(crate) fn ulps_eq<T: Ulps>(a: T, b: T, eps: T, max_ulps: u64) -> bool {
+    if (a - b).abs() <= eps {
+        return true;
+    }
+    matches!(a.ulps_distance(b), Some(d) if d <= max_ulps)
+}
+
+#[cfg(feature = "approx")]
+mod approx_traits {
+    use crate::Point;
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+    impl<T> AbsDiffEq for Point<T>
+    where
+        T: AbsDiffEq + Clone,
+        T::Epsilon: Copy,
+    {
+        type Epsilon = T::Epsilon;
+
+        fn default_epsilon() -> T::Epsilon {
+            T::default_epsilon()
+        }
+
+        fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+            self.get_size() == other.get_size()
+                && self
+                    .get_vector()
+                    .iter()
+                    .zip(other.get_vector().iter())
+                    .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+        }
+    }
+
+    impl<T> RelativeEq for Point<T>
+    where
+        T: RelativeEq + Clone,
+        T::Epsilon: Copy,
+    {
+        fn default_max_relative() -> T::Epsilon {
+            T::default_max_relative()
+        }
+
+        fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+            self.get_size() == other.get_size()
+                && self
+                    .get_vector()
+                    .iter()
+                    .zip(other.get_vector().iter())
+                    .all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+        }
+    }
+
+    impl<T> UlpsEq for Point<T>
+    where
+        T: UlpsEq + Clone,
+        T::Epsilon: Copy,
+    {
+        fn default_max_ulps() -> u32 {
+            T::default_max_ulps()
+        }
+
+        fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+            self.get_size() == other.get_size()
+                && self
+                    .get_vector()
+                    .iter()
+                    .zip(other.get_vector().iter())
+                    .all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Conversions between Cartesian points and angular coordinates. Angles are in radians.
+use crate::Point;
+use num::Float;
+
+fn check_dim(expected: usize, found: usize) {
+    if expected != found {
+        panic!(
+            "expected a point with {} dimensions, found {}",
+            expected, found
+        );
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Float,
+{
+    /// Convert a 2D Cartesian point `(x, y)` to polar coordinates `(r, theta)`, where `theta` is
+    /// the angle from the x axis, in `(-pi, pi]`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![0.0, 2.0]);
+    /// let polar = p1.to_polar();
+    /// assert_eq!(polar.get_vector(), &vec![2.0, std::f64::consts::FRAC_PI_2]);
+    /// assert!(Point::from_polar(&polar).close(&p1, 1e-12));
+    /// ```
+    /// # Panic
+    /// This function will panic if the point is not 2-dimensional.
+    pub fn to_polar(&self) -> Point<T> {
+        check_dim(2, self.get_size());
+        let (x, y) = (*self.get_value(1), *self.get_value(2));
+        Point::new_from_vec(&vec![x.hypot(y), y.atan2(x)])
+    }
+
+    /// Convert polar coordinates `(r, theta)` to a 2D Cartesian point (see
+    /// [`Point::to_polar`]).
+    /// # Panic
+    /// This function will panic if the point is not 2-dimensional.
+    pub fn from_polar(polar: &Point<T>) -> Point<T> {
+        check_dim(2, polar.get_size());
+        let (r, theta) = (*polar.get_value(1), *polar.get_value(2));
+        Point::new_from_vec(&vec![r * theta.cos(), r * theta.sin()])
+    }
+
+    /// Convert a 3D Cartesian point `(x, y, z)` to spherical coordinates `(r, theta, phi)`:
+    /// `theta` is the angle from the z axis (in `[0, pi]`), and `phi` is the angle of the
+    /// projection on the xy plane from the x axis (in `(-pi, pi]`).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 0.0, 1.0]);
+    /// let spherical = p1.to_spherical();
+    /// assert!(spherical.close(
+    ///     &Point::new_from_vec(&vec![2f64.sqrt(), std::f64::consts::FRAC_PI_4, 0.0]),
+    ///     1e-12
+    /// ));
+    /// assert!(Point::from_spherical(&spherical).close(&p1, 1e-12));
+    /// ```
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn to_spherical(&self) -> Point<T> {
+        check_dim(3, self.get_size());
+        let (x, y, z) = (*self.get_value(1), *self.get_value(2), *self.get_value(3));
+        let rho = x.hypot(y);
+        Point::new_from_vec(&vec![rho.hypot(z), rho.atan2(z), y.atan2(x)])
+    }
+
+    /// Convert spherical coordinates `(r, theta, phi)` to a 3D Cartesian point (see
+    /// [`Point::to_spherical`]).
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn from_spherical(spherical: &Point<T>) -> Point<T> {
+        check_dim(3, spherical.get_size());
+        let (r, theta, phi) = (
+            *spherical.get_value(1),
+            *spherical.get_value(2),
+            *spherical.get_value(3),
+        );
+        Point::new_from_vec(&vec![
+            r * theta.sin() * phi.cos(),
+            r * theta.sin() * phi.sin(),
+            r * theta.cos(),
+        ])
+    }
+
+    /// Convert a 3D Cartesian point `(x, y, z)` to cylindrical coordinates `(rho, phi, z)`,
+    /// where `(rho, phi)` are the polar coordinates of `(x, y)`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![-2.0, 0.0, 5.0]);
+    /// let cylindrical = p1.to_cylindrical();
+    /// assert_eq!(cylindrical.get_vector(), &vec![2.0, std::f64::consts::PI, 5.0]);
+    /// assert!(Point::from_cylindrical(&cylindrical).close(&p1, 1e-12));
+    /// ```
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn to_cylindrical(&self) -> Point<T> {
+        check_dim(3, self.get_size());
+        let (x, y, z) = (*self.get_value(1), *self.get_value(2), *self.get_value(3));
+        Point::new_from_vec(&vec![x.hypot(y), y.atan2(x), z])
+    }
+
+    /// Convert cylindrical coordinates `(rho, phi, z)` to a 3D Cartesian point (see
+    /// [`Point::to_cylindrical`]).
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn from_cylindrical(cylindrical: &Point<T>) -> Point<T> {
+        check_dim(3, cylindrical.get_size());
+        let (rho, phi, z) = (
+            *cylindrical.get_value(1),
+            *cylindrical.get_value(2),
+            *cylindrical.get_value(3),
+        );
+        Point::new_from_vec(&vec![rho * phi.cos(), rho * phi.sin(), z])
+    }
+
+    /// Convert an N-dimensional Cartesian point to hyperspherical coordinates
+    /// `(r, phi(1), ..., phi(N-1))`, where
+    ///
+    /// ```text
+    /// x(1)   = r cos(phi(1))
+    /// x(2)   = r sin(phi(1)) cos(phi(2))
+    /// ...
+    /// x(N-1) = r sin(phi(1)) ... sin(phi(N-2)) cos(phi(N-1))
+    /// x(N)   = r sin(phi(1)) ... sin(phi(N-2)) sin(phi(N-1))
+    /// ```
+    ///
+    /// The angles `phi(1)` to `phi(N-2)` are in `[0, pi]`, and `phi(N-1)` is in `(-pi, pi]`. In
+    /// 2D these are the polar coordinates. When the remaining coordinates are all zero the
+    /// angle is not unique, and 0 is returned.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 2.0, -3.0, 4.0]);
+    /// let hyperspherical = p1.to_hyperspherical();
+    /// assert!((hyperspherical.get_value(1) - 30f64.sqrt()).abs() < 1e-12);
+    /// assert!(Point::from_hyperspherical(&hyperspherical).close(&p1, 1e-12));
+    /// ```
+    /// # Panic
+    /// This function will panic if the point has less than 2 dimensions.
+    pub fn to_hyperspherical(&self) -> Point<T> {
+        let n = self.get_size();
+        if n < 2 {
+            panic!("expected a point with at least 2 dimensions, found {}", n);
+        }
+        let x = self.get_vector();
+        // tail[k] = sqrt(x(k)^2 + ... + x(N)^2), with 0-based k.
+        let mut tail = vec![T::zero(); n + 1];
+        for k in (0..n).rev() {
+            tail[k] = tail[k + 1].hypot(x[k]);
+        }
+        let mut values = Vec::with_capacity(n);
+        values.push(tail[0]);
+        for k in 0..n - 2 {
+            values.push(tail[k + 1].atan2(x[k]));
+        }
+        values.push(x[n - 1].atan2(x[n - 2]));
+        Point::new_from_vec(&values)
+    }
+
+    /// Convert hyperspherical coordinates `(r, phi(1), ..., phi(N-1))` to an N-dimensional
+    /// Cartesian point (see [`Point::to_hyperspherical`]).
+    /// # Panic
+    /// This function will panic if the point has less than 2 dimensions.
+    pub fn from_hyperspherical(hyperspherical: &Point<T>) -> Point<T> {
+        let n = hyperspherical.get_size();
+        if n < 2 {
+            panic!("expected a point with at least 2 dimensions, found {}", n);
+        }
+        let values = hyperspherical.get_vector();
+        let mut x = Vec::with_capacity(n);
+        // r * sin(phi(1)) * ... * sin(phi(k - 1))
+        let mut product = values[0];
+        for phi in &values[1..] {
+            x.push(product * phi.cos());
+            product = product * phi.sin();
+        }
+        x.push(product);
+        Point::new_from_vec(&x)
+    }
+}
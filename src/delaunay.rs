@@ -0,0 +1,482 @@
+//! Delaunay triangulation of 2D and 3D points.
+use crate::predicates::{insphere, orient};
+use crate::simplex::Simplex;
+use crate::space_filling::hilbert_encode;
+use crate::Point;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// The vertex "at infinity", which closes the triangulation around the convex hull.
+const INFINITE: usize = usize::MAX;
+
+/// A cell of the triangulation under construction: `d + 1` vertices, and the neighbour opposite
+/// each vertex.
+#[derive(Debug, Clone)]
+struct Cell {
+    vertices: Vec<usize>,
+    neighbours: Vec<usize>,
+    alive: bool,
+}
+
+impl Cell {
+    fn infinite_position(&self) -> Option<usize> {
+        self.vertices.iter().position(|v| *v == INFINITE)
+    }
+}
+
+/// The state of the Bowyer-Watson algorithm.
+struct Builder<'a> {
+    points: &'a [Point<f64>],
+    cells: Vec<Cell>,
+    free: Vec<usize>,
+    /// A live cell, where the walks start.
+    hint: usize,
+}
+
+impl<'a> Builder<'a> {
+    fn coordinates(&self, vertex: usize) -> &'a [f64] {
+        self.points[vertex].get_vector()
+    }
+
+    /// The orientation of the cell, with `point` instead of the vertex in `position`.
+    fn orient_with(&self, cell: &Cell, position: usize, point: &[f64]) -> Ordering {
+        let coordinates: Vec<&[f64]> = cell
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                if i == position {
+                    point
+                } else {
+                    self.coordinates(*v)
+                }
+            })
+            .collect();
+        orient(&coordinates)
+    }
+
+    /// `true` if the point is strictly inside the circumsphere of the cell. An infinite cell's
+    /// "circumsphere" is the open half-space outside its hull facet.
+    fn in_conflict(&self, cell: usize, point: &[f64]) -> bool {
+        let c = &self.cells[cell];
+        match c.infinite_position() {
+            None => {
+                let coordinates: Vec<&[f64]> =
+                    c.vertices.iter().map(|v| self.coordinates(*v)).collect();
+                insphere(&coordinates, point) == Ordering::Greater
+            }
+            Some(k) => match self.orient_with(c, k, point) {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                // on the plane of the hull facet: in the circumsphere of the facet, which is
+                // where the plane cuts the circumsphere of the finite cell behind the facet.
+                Ordering::Equal => self.in_conflict(c.neighbours[k], point),
+            },
+        }
+    }
+
+    /// A cell in conflict with the point, by a visibility walk from the hint.
+    fn locate(&self, point: &[f64]) -> usize {
+        let mut cell = self.hint;
+        if self.cells[cell].infinite_position().is_some() {
+            cell = self.cells[cell].neighbours[self.cells[cell].infinite_position().unwrap()];
+        }
+        'walk: loop {
+            let c = &self.cells[cell];
+            if c.infinite_position().is_some() {
+                return cell;
+            }
+            for i in 0..c.vertices.len() {
+                if self.orient_with(c, i, point) == Ordering::Less {
+                    cell = c.neighbours[i];
+                    continue 'walk;
+                }
+            }
+            return cell;
+        }
+    }
+
+    fn add_cell(&mut self, cell: Cell) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.cells[index] = cell;
+                index
+            }
+            None => {
+                self.cells.push(cell);
+                self.cells.len() - 1
+            }
+        }
+    }
+
+    /// Link the new cells to each other, through the ridges (facets without one vertex) that
+    /// they share.
+    fn link(&mut self, new_cells: &[usize]) {
+        let mut ridges: HashMap<Vec<usize>, (usize, usize)> = HashMap::new();
+        for cell in new_cells {
+            for position in 0..self.cells[*cell].vertices.len() {
+                if self.cells[*cell].neighbours[position] != INFINITE {
+                    continue;
+                }
+                let mut ridge: Vec<usize> = self.cells[*cell]
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != position)
+                    .map(|(_, v)| *v)
+                    .collect();
+                ridge.sort_unstable();
+                match ridges.remove(&ridge) {
+                    Some((other, other_position)) => {
+                        self.cells[*cell].neighbours[position] = other;
+                        self.cells[other].neighbours[other_position] = *cell;
+                    }
+                    None => {
+                        ridges.insert(ridge, (*cell, position));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Insert the vertex, replacing the cells in conflict with it by cells connecting it to the
+    /// boundary of the conflict region. Duplicated points are skipped.
+    fn insert(&mut self, vertex: usize) {
+        let point = self.coordinates(vertex);
+        let start = self.locate(point);
+        let c = &self.cells[start];
+        if c.vertices
+            .iter()
+            .any(|v| *v != INFINITE && self.coordinates(*v) == point)
+        {
+            return;
+        }
+        // the conflict region
+        let mut conflict = vec![start];
+        let mut in_region: HashMap<usize, bool> = HashMap::new();
+        in_region.insert(start, true);
+        let mut i = 0;
+        while i < conflict.len() {
+            let cell = conflict[i];
+            for n in self.cells[cell].neighbours.clone() {
+                if let Entry::Vacant(entry) = in_region.entry(n) {
+                    let inside = self.in_conflict(n, point);
+                    entry.insert(inside);
+                    if inside {
+                        conflict.push(n);
+                    }
+                }
+            }
+            i += 1;
+        }
+        // a new cell for each facet on the boundary of the region
+        let mut new_cells = Vec::new();
+        for cell in &conflict {
+            for position in 0..self.cells[*cell].vertices.len() {
+                let outside = self.cells[*cell].neighbours[position];
+                if in_region[&outside] {
+                    continue;
+                }
+                let mut vertices = self.cells[*cell].vertices.clone();
+                vertices[position] = vertex;
+                let mut neighbours = vec![INFINITE; vertices.len()];
+                neighbours[position] = outside;
+                let new_cell = self.add_cell(Cell {
+                    vertices,
+                    neighbours,
+                    alive: true,
+                });
+                let back = self.cells[outside]
+                    .neighbours
+                    .iter()
+                    .position(|n| n == cell)
+                    .unwrap();
+                self.cells[outside].neighbours[back] = new_cell;
+                new_cells.push(new_cell);
+            }
+        }
+        for cell in &conflict {
+            self.cells[*cell].alive = false;
+        }
+        self.free.extend(conflict);
+        self.link(&new_cells);
+        self.hint = new_cells[0];
+    }
+}
+
+/// The first `d + 1` affinely independent points, as a positively oriented simplex.
+fn initial_simplex(points: &[Point<f64>], order: &[usize]) -> Option<Vec<usize>> {
+    let dim = points[0].get_size();
+    let coordinates = |i: usize| points[i].get_vector().as_slice();
+    let first = order[0];
+    let second = *order
+        .iter()
+        .find(|i| coordinates(**i) != coordinates(first))?;
+    // a third point not on the line of the first two (in 3D, not collinear in any projection).
+    let third = *order.iter().find(|i| {
+        (0..dim).any(|a| {
+            (a + 1..dim).any(|b| {
+                let project = |p: usize| [coordinates(p)[a], coordinates(p)[b]];
+                let (p, q, r) = (project(first), project(second), project(**i));
+                orient(&[&p, &q, &r]) != Ordering::Equal
+            })
+        })
+    })?;
+    let mut simplex = vec![first, second, third];
+    if dim == 3 {
+        let fourth = *order.iter().find(|i| {
+            orient(&[
+                coordinates(first),
+                coordinates(second),
+                coordinates(third),
+                coordinates(**i),
+            ]) != Ordering::Equal
+        })?;
+        simplex.push(fourth);
+    }
+    let simplex_coordinates: Vec<&[f64]> = simplex.iter().map(|v| coordinates(*v)).collect();
+    if orient(&simplex_coordinates) == Ordering::Less {
+        simplex.swap(0, 1);
+    }
+    Some(simplex)
+}
+
+/// A Delaunay triangulation of 2D or 3D points: simplices (triangles or tetrahedra) whose
+/// circumspheres don't contain any of the points.
+///
+/// Built incrementally with the Bowyer-Watson algorithm, inserting the points in the order of a
+/// Hilbert curve, with exact orientation and insphere predicates (so degenerate inputs, like
+/// points on a grid, are triangulated correctly). Duplicated points are triangulated once (the
+/// first of them is a vertex); if all the points are collinear (or coplanar in 3D) there are no
+/// simplices.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::delaunay::Delaunay;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![2.0, 0.0]),
+///     Point::new_from_vec(&vec![0.0, 2.0]),
+///     Point::new_from_vec(&vec![2.0, 2.0]),
+///     Point::new_from_vec(&vec![1.0, 0.9]),
+/// ];
+/// let triangulation = Delaunay::new(&points);
+/// assert_eq!(triangulation.simplices().len(), 4);
+/// let found = triangulation.locate(&Point::new_from_vec(&vec![1.0, 0.2])).unwrap();
+/// assert!(triangulation.simplices()[found].contains(&0));
+/// assert!(triangulation.locate(&Point::new_from_vec(&vec![3.0, 0.2])).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Delaunay {
+    points: Vec<Point<f64>>,
+    simplices: Vec<Vec<usize>>,
+    neighbours: Vec<Vec<Option<usize>>>,
+}
+
+impl Delaunay {
+    /// Triangulate the points.
+    /// # Panic
+    /// This function will panic if the points are not 2 or 3 dimensional, if their dimensions
+    /// are not equal, or if a value is not finite.
+    pub fn new(points: &[Point<f64>]) -> Delaunay {
+        let mut triangulation = Delaunay {
+            points: points.to_vec(),
+            simplices: Vec::new(),
+            neighbours: Vec::new(),
+        };
+        let dim = match points.first() {
+            Some(first) => first.get_size(),
+            None => return triangulation,
+        };
+        if dim != 2 && dim != 3 {
+            panic!(
+                "Delaunay triangulation supports 2 and 3 dimensions, found {}",
+                dim
+            );
+        }
+        if points.iter().any(|p| p.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        if points
+            .iter()
+            .any(|p| p.get_vector().iter().any(|v| !v.is_finite()))
+        {
+            panic!("the values of the points must be finite");
+        }
+        let order = hilbert_order(points);
+        let simplex = match initial_simplex(points, &order) {
+            Some(simplex) => simplex,
+            None => return triangulation,
+        };
+        let mut builder = Builder {
+            points,
+            cells: Vec::new(),
+            free: Vec::new(),
+            hint: 0,
+        };
+        // the simplex and an infinite cell on each of its facets
+        builder.cells.push(Cell {
+            vertices: simplex.clone(),
+            neighbours: (1..=dim + 1).collect(),
+            alive: true,
+        });
+        for position in 0..=dim {
+            let mut vertices = simplex.clone();
+            vertices[position] = INFINITE;
+            // keep "outside the facet" positive, by swapping two other vertices.
+            let others: Vec<usize> = (0..=dim).filter(|i| *i != position).collect();
+            vertices.swap(others[0], others[1]);
+            let mut neighbours = vec![INFINITE; dim + 1];
+            neighbours[position] = 0;
+            builder.cells.push(Cell {
+                vertices,
+                neighbours,
+                alive: true,
+            });
+        }
+        builder.link(&(1..=dim + 1).collect::<Vec<usize>>());
+        for vertex in order {
+            if !simplex.contains(&vertex) {
+                builder.insert(vertex);
+            }
+        }
+        // keep the finite cells
+        let mut index = vec![None; builder.cells.len()];
+        for (i, cell) in builder.cells.iter().enumerate() {
+            if cell.alive && cell.infinite_position().is_none() {
+                index[i] = Some(triangulation.simplices.len());
+                triangulation.simplices.push(cell.vertices.clone());
+            }
+        }
+        triangulation.neighbours = builder
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| index[*i].is_some())
+            .map(|(_, cell)| cell.neighbours.iter().map(|n| index[*n]).collect())
+            .collect();
+        triangulation
+    }
+
+    /// The triangulated points.
+    pub fn points(&self) -> &[Point<f64>] {
+        &self.points
+    }
+
+    /// The simplices, as indices of their `d + 1` vertices in the points. The simplices are
+    /// positively oriented (counterclockwise in 2D).
+    pub fn simplices(&self) -> &[Vec<usize>] {
+        &self.simplices
+    }
+
+    /// The simplex, as a `Simplex` of its points.
+    /// # Panic
+    /// This function will panic if the index is out of range.
+    pub fn simplex(&self, index: usize) -> Simplex<f64> {
+        let vertices: Vec<Point<f64>> = self.simplices[index]
+            .iter()
+            .map(|v| self.points[*v].clone())
+            .collect();
+        Simplex::new(&vertices)
+    }
+
+    /// The neighbours of each simplex: the neighbour in position `i` is the simplex across the
+    /// facet opposite vertex `i`, or `None` on the convex hull.
+    pub fn neighbours(&self) -> &[Vec<Option<usize>>] {
+        &self.neighbours
+    }
+
+    /// The facets of the convex hull, as indices of their `d` vertices.
+    pub fn hull_facets(&self) -> Vec<Vec<usize>> {
+        let mut facets = Vec::new();
+        for (simplex, neighbours) in self.simplices.iter().zip(&self.neighbours) {
+            for (i, n) in neighbours.iter().enumerate() {
+                if n.is_none() {
+                    let mut facet = simplex.clone();
+                    facet.remove(i);
+                    facets.push(facet);
+                }
+            }
+        }
+        facets
+    }
+
+    /// The index of a simplex that contains the point (including its boundary), or `None` if the
+    /// point is outside the convex hull.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the triangulation are not
+    /// equal.
+    pub fn locate(&self, point: &Point<f64>) -> Option<usize> {
+        if self.simplices.is_empty() {
+            return None;
+        }
+        if point.get_size() != self.points[0].get_size() {
+            panic!("dimensions are not equal");
+        }
+        let p = point.get_vector().as_slice();
+        let mut simplex = 0;
+        'walk: loop {
+            for i in 0..self.simplices[simplex].len() {
+                let coordinates: Vec<&[f64]> = self.simplices[simplex]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, v)| {
+                        if i == j {
+                            p
+                        } else {
+                            self.points[*v].get_vector().as_slice()
+                        }
+                    })
+                    .collect();
+                if orient(&coordinates) == Ordering::Less {
+                    simplex = self.neighbours[simplex][i]?;
+                    continue 'walk;
+                }
+            }
+            return Some(simplex);
+        }
+    }
+}
+
+/// The indices of the points, in the order of a Hilbert curve over their bounding box.
+fn hilbert_order(points: &[Point<f64>]) -> Vec<usize> {
+    let dim = points[0].get_size();
+    let bits = 16;
+    let (min, max): (Vec<f64>, Vec<f64>) = (0..dim)
+        .map(|i| {
+            points
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                    (lo.min(p.get_vector()[i]), hi.max(p.get_vector()[i]))
+                })
+        })
+        .unzip();
+    let cells = ((1u32 << bits) - 1) as f64;
+    let mut keys: Vec<(u128, usize)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let cell: Vec<u32> = p
+                .get_vector()
+                .iter()
+                .enumerate()
+                .map(|(d, v)| {
+                    let extent = max[d] - min[d];
+                    if extent > 0.0 {
+                        ((v - min[d]) / extent * cells) as u32
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            (
+                hilbert_encode(&Point::new_from_vec(&cell), bits).unwrap(),
+                i,
+            )
+        })
+        .collect();
+    keys.sort_unstable();
+    keys.into_iter().map(|(_, i)| i).collect()
+}
@@ -0,0 +1,282 @@
+//! Geographic points: `(latitude, longitude)` or `(latitude, longitude, altitude)` points, with
+//! the angles in degrees and the altitude (above the WGS84 ellipsoid) and distances in meters.
+//!
+//! # Example
+//! ```
+//! use multi_dim_point::geo;
+//! use multi_dim_point::Point;
+//! let paris = Point::new_from_vec(&vec![48.8566, 2.3522]);
+//! let london = Point::new_from_vec(&vec![51.5074, -0.1278]);
+//! let distance = geo::haversine_distance(&paris, &london);
+//! assert!((distance - 343_556.0).abs() < 100.0);
+//! ```
+use crate::Point;
+
+/// The mean radius of the Earth, in meters (used by the spherical functions).
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+/// The semi-major axis of the WGS84 ellipsoid, in meters.
+pub const WGS84_A: f64 = 6_378_137.0;
+/// The flattening of the WGS84 ellipsoid.
+pub const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Latitude and longitude of the point, in radians.
+fn lat_lon(p: &Point<f64>) -> (f64, f64) {
+    let size = p.get_size();
+    if size != 2 && size != 3 {
+        panic!(
+            "expected a (lat, lon) or (lat, lon, alt) point, found {} dimensions",
+            size
+        );
+    }
+    (p.get_value(1).to_radians(), p.get_value(2).to_radians())
+}
+
+fn altitude(p: &Point<f64>) -> f64 {
+    if p.get_size() == 3 {
+        *p.get_value(3)
+    } else {
+        0.0
+    }
+}
+
+/// A point with the same dimension (and altitude) as `like`.
+fn geo_point(lat: f64, lon: f64, like: &Point<f64>) -> Point<f64> {
+    // normalize the longitude to [-180, 180).
+    let lon = (lon.to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+    let mut values = vec![lat.to_degrees(), lon];
+    if like.get_size() == 3 {
+        values.push(altitude(like));
+    }
+    Point::new_from_vec(&values)
+}
+
+fn normalize_bearing(radians: f64) -> f64 {
+    radians.to_degrees().rem_euclid(360.0)
+}
+
+/// Great-circle distance between the points on a sphere with the mean radius of the Earth, by
+/// the haversine formula. The altitude is ignored.
+/// # Panic
+/// This function will panic if a point doesn't have 2 or 3 dimensions.
+pub fn haversine_distance(a: &Point<f64>, b: &Point<f64>) -> f64 {
+    let (lat1, lon1) = lat_lon(a);
+    let (lat2, lon2) = lat_lon(b);
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().min(1.0).asin()
+}
+
+/// Geodesic distance between the points on the WGS84 ellipsoid, by Vincenty's inverse formula
+/// (accurate to less than a millimeter). The altitude is ignored. Returns `None` when the
+/// formula doesn't converge, which can happen for nearly antipodal points.
+/// # Example
+/// ```
+/// use multi_dim_point::geo;
+/// use multi_dim_point::Point;
+/// let a = Point::new_from_vec(&vec![0.0, 0.0]);
+/// let b = Point::new_from_vec(&vec![0.0, 1.0]);
+/// let distance = geo::vincenty_distance(&a, &b).unwrap();
+/// assert!((distance - 111_319.491).abs() < 0.001);
+/// ```
+/// # Panic
+/// This function will panic if a point doesn't have 2 or 3 dimensions.
+pub fn vincenty_distance(a: &Point<f64>, b: &Point<f64>) -> Option<f64> {
+    let (lat1, lon1) = lat_lon(a);
+    let (lat2, lon2) = lat_lon(b);
+    let f = WGS84_F;
+    let b_axis = WGS84_A * (1.0 - f);
+    let l = lon2 - lon1;
+    let u1 = ((1.0 - f) * lat1.tan()).atan();
+    let u2 = ((1.0 - f) * lat2.tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            return Some(0.0); // the same point.
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        // on the equator cos2_alpha is 0.
+        let cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+        let previous = lambda;
+        lambda = l
+            + (1.0 - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m
+                            + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+        if (lambda - previous).abs() < 1e-12 {
+            let u2 = cos2_alpha * (WGS84_A * WGS84_A - b_axis * b_axis) / (b_axis * b_axis);
+            let big_a = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+            let big_b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+            let delta_sigma = big_b
+                * sin_sigma
+                * (cos_2sigma_m
+                    + big_b / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)
+                            - big_b / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                                * (-3.0 + 4.0 * cos_2sigma_m * cos_2sigma_m)));
+            return Some(b_axis * big_a * (sigma - delta_sigma));
+        }
+    }
+    None
+}
+
+/// The initial bearing (forward azimuth) of the great circle from `a` to `b`, in degrees
+/// clockwise from north, in `[0, 360)`.
+/// # Example
+/// ```
+/// use multi_dim_point::geo;
+/// use multi_dim_point::Point;
+/// let a = Point::new_from_vec(&vec![0.0, 0.0]);
+/// let b = Point::new_from_vec(&vec![0.0, 10.0]);
+/// assert!((geo::initial_bearing(&a, &b) - 90.0).abs() < 1e-12);
+/// ```
+/// # Panic
+/// This function will panic if a point doesn't have 2 or 3 dimensions.
+pub fn initial_bearing(a: &Point<f64>, b: &Point<f64>) -> f64 {
+    let (lat1, lon1) = lat_lon(a);
+    let (lat2, lon2) = lat_lon(b);
+    let dlon = lon2 - lon1;
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    normalize_bearing(y.atan2(x))
+}
+
+/// The final bearing of the great circle from `a` to `b` (the bearing when arriving to `b`), in
+/// degrees clockwise from north, in `[0, 360)`.
+/// # Panic
+/// This function will panic if a point doesn't have 2 or 3 dimensions.
+pub fn final_bearing(a: &Point<f64>, b: &Point<f64>) -> f64 {
+    (initial_bearing(b, a) + 180.0).rem_euclid(360.0)
+}
+
+/// The point reached from `start` after moving `distance` meters along the great circle in the
+/// initial `bearing` (degrees clockwise from north). The altitude of `start` is kept.
+/// # Example
+/// ```
+/// use multi_dim_point::geo;
+/// use multi_dim_point::Point;
+/// let start = Point::new_from_vec(&vec![10.0, 20.0, 100.0]);
+/// let end = geo::destination(&start, 45.0, 50_000.0);
+/// assert!((geo::haversine_distance(&start, &end) - 50_000.0).abs() < 1e-6);
+/// assert_eq!(end.get_value(3), &100.0);
+/// ```
+/// # Panic
+/// This function will panic if the point doesn't have 2 or 3 dimensions.
+pub fn destination(start: &Point<f64>, bearing: f64, distance: f64) -> Point<f64> {
+    let (lat1, lon1) = lat_lon(start);
+    let delta = distance / EARTH_RADIUS;
+    let theta = bearing.to_radians();
+    let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * theta.cos()).asin();
+    let lon2 = lon1
+        + (theta.sin() * delta.sin() * lat1.cos()).atan2(delta.cos() - lat1.sin() * lat2.sin());
+    geo_point(lat2, lon2, start)
+}
+
+/// The midpoint of the great circle between the points. For 3D points, the altitude is the
+/// average altitude.
+/// # Example
+/// ```
+/// use multi_dim_point::geo;
+/// use multi_dim_point::Point;
+/// let a = Point::new_from_vec(&vec![0.0, 0.0]);
+/// let b = Point::new_from_vec(&vec![0.0, 90.0]);
+/// assert!(geo::midpoint(&a, &b).close(&Point::new_from_vec(&vec![0.0, 45.0]), 1e-12));
+/// ```
+/// # Panic
+/// This function will panic if a point doesn't have 2 or 3 dimensions, or if the dimensions of
+/// the points are not equal.
+pub fn midpoint(a: &Point<f64>, b: &Point<f64>) -> Point<f64> {
+    if a.get_size() != b.get_size() {
+        panic!("dimensions are not equal");
+    }
+    let (lat1, lon1) = lat_lon(a);
+    let (lat2, lon2) = lat_lon(b);
+    let dlon = lon2 - lon1;
+    let bx = lat2.cos() * dlon.cos();
+    let by = lat2.cos() * dlon.sin();
+    let lat = (lat1.sin() + lat2.sin()).atan2(((lat1.cos() + bx).powi(2) + by * by).sqrt());
+    let lon = lon1 + by.atan2(lat1.cos() + bx);
+    let mut mid = geo_point(lat, lon, a);
+    if a.get_size() == 3 {
+        mid.set_value(3, &((altitude(a) + altitude(b)) / 2.0));
+    }
+    mid
+}
+
+/// Convert a geographic point (on the WGS84 ellipsoid) to Earth-centered, Earth-fixed (ECEF)
+/// Cartesian coordinates `(x, y, z)`, in meters. A 2D point is on the ellipsoid's surface.
+/// # Example
+/// ```
+/// use multi_dim_point::geo;
+/// use multi_dim_point::Point;
+/// let p = Point::new_from_vec(&vec![0.0, 90.0, 10.0]);
+/// let ecef = geo::to_ecef(&p);
+/// assert!(ecef.close(&Point::new_from_vec(&vec![0.0, 6_378_147.0, 0.0]), 1e-6));
+/// assert!(geo::from_ecef(&ecef).close(&p, 1e-6));
+/// ```
+/// # Panic
+/// This function will panic if the point doesn't have 2 or 3 dimensions.
+pub fn to_ecef(p: &Point<f64>) -> Point<f64> {
+    let (lat, lon) = lat_lon(p);
+    let h = altitude(p);
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let n = WGS84_A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    Point::new_from_vec(&vec![
+        (n + h) * lat.cos() * lon.cos(),
+        (n + h) * lat.cos() * lon.sin(),
+        (n * (1.0 - e2) + h) * lat.sin(),
+    ])
+}
+
+/// Convert Earth-centered, Earth-fixed (ECEF) Cartesian coordinates to a geographic
+/// `(latitude, longitude, altitude)` point (on the WGS84 ellipsoid).
+/// # Panic
+/// This function will panic if the point is not 3-dimensional.
+pub fn from_ecef(ecef: &Point<f64>) -> Point<f64> {
+    if ecef.get_size() != 3 {
+        panic!(
+            "expected a point with 3 dimensions, found {}",
+            ecef.get_size()
+        );
+    }
+    let (x, y, z) = (*ecef.get_value(1), *ecef.get_value(2), *ecef.get_value(3));
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let p = x.hypot(y);
+    let lon = y.atan2(x);
+    // iterate on the latitude, starting from the spherical approximation.
+    let mut lat = z.atan2(p * (1.0 - e2));
+    let mut h = 0.0;
+    for _ in 0..10 {
+        let n = WGS84_A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+        h = if lat.cos().abs() > 1e-10 {
+            p / lat.cos() - n
+        } else {
+            z.abs() - n * (1.0 - e2)
+        };
+        let next = z.atan2(p * (1.0 - e2 * n / (n + h)));
+        let converged = (next - lat).abs() < 1e-15;
+        lat = next;
+        if converged {
+            break;
+        }
+    }
+    Point::new_from_vec(&vec![lat.to_degrees(), lon.to_degrees(), h])
+}
@@ -0,0 +1,279 @@
+//! Hyperplanes (lines in 2D, planes in 3D, ...) in N-dimensional space.
+use crate::linalg::determinant;
+use crate::Point;
+use num::Float;
+
+/// The side of a hyperplane that a point is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// In the direction of the normal.
+    Positive,
+    /// Against the direction of the normal.
+    Negative,
+    /// On the hyperplane (within the tolerance).
+    On,
+}
+
+/// A hyperplane: the points `x` with `normal . x = offset`, where the normal has unit length.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::hyperplane::{Hyperplane, Side};
+/// use multi_dim_point::Point;
+/// // the line y = 1
+/// let line = Hyperplane::from_point_normal(
+///     &Point::new_from_vec(&vec![0.0, 1.0]),
+///     &Point::new_from_vec(&vec![0.0, 2.0]),
+/// )
+/// .unwrap();
+/// let p = Point::new_from_vec(&vec![3.0, 4.0]);
+/// assert_eq!(line.signed_distance(&p), 3.0);
+/// assert_eq!(line.project(&p).get_vector(), &vec![3.0, 1.0]);
+/// assert_eq!(line.reflect(&p).get_vector(), &vec![3.0, -2.0]);
+/// assert_eq!(line.side(&p, 1e-9), Side::Positive);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hyperplane<T> {
+    normal: Point<T>,
+    offset: T,
+}
+
+impl<T> Hyperplane<T>
+where
+    T: Float,
+{
+    /// Creates the hyperplane `normal . x = offset`. Both are scaled so the normal has unit
+    /// length. `None` if the normal is zero.
+    pub fn new(normal: &Point<T>, offset: T) -> Option<Hyperplane<T>> {
+        let length = normal.norm();
+        if length.is_zero() || !length.is_finite() {
+            return None;
+        }
+        let values: Vec<T> = normal.get_vector().iter().map(|v| *v / length).collect();
+        Some(Hyperplane {
+            normal: Point::new_from_vec(&values),
+            offset: offset / length,
+        })
+    }
+
+    /// Creates the hyperplane through the point, perpendicular to the normal. `None` if the
+    /// normal is zero.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the normal are not equal.
+    pub fn from_point_normal(point: &Point<T>, normal: &Point<T>) -> Option<Hyperplane<T>> {
+        Hyperplane::new(normal, normal.dot(point))
+    }
+
+    /// Creates the hyperplane through N points in N dimensions. The normal points to the side of
+    /// the points `q` for which the simplex of the points and `q` is positively oriented (see
+    /// [`Simplex::signed_volume`](crate::simplex::Simplex::signed_volume); in 2D, to the left of
+    /// the line from the first point to the second). `None` if the points are affinely
+    /// dependent.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::hyperplane::Hyperplane;
+    /// use multi_dim_point::Point;
+    /// let plane = Hyperplane::from_points(&[
+    ///     Point::new_from_vec(&vec![1.0, 0.0, 0.0]),
+    ///     Point::new_from_vec(&vec![0.0, 1.0, 0.0]),
+    ///     Point::new_from_vec(&vec![0.0, 0.0, 1.0]),
+    /// ])
+    /// .unwrap();
+    /// let origin = Point::new_from_vec(&vec![0.0, 0.0, 0.0]);
+    /// assert!((plane.signed_distance(&origin) - 1.0 / 3.0_f64.sqrt()).abs() < 1e-12);
+    /// ```
+    /// # Panic
+    /// This function will panic if there are not N points, where N is their dimension, or if the
+    /// dimensions of the points are not equal.
+    pub fn from_points(points: &[Point<T>]) -> Option<Hyperplane<T>> {
+        let dim = match points.first() {
+            Some(first) => first.get_size(),
+            None => panic!("a hyperplane needs at least one point"),
+        };
+        if points.iter().any(|p| p.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        if points.len() != dim {
+            panic!(
+                "a hyperplane in {} dimensions needs {} points, found {}",
+                dim,
+                dim,
+                points.len()
+            );
+        }
+        let origin = points[0].get_vector();
+        let edges: Vec<Vec<T>> = points[1..]
+            .iter()
+            .map(|p| {
+                p.get_vector()
+                    .iter()
+                    .zip(origin)
+                    .map(|(a, b)| *a - *b)
+                    .collect()
+            })
+            .collect();
+        // the generalized cross product of the edges, signed so that `normal . (q - points[0])`
+        // is the determinant of the rows `points[i] - q`, the orientation of the simplex of the
+        // points and `q`.
+        let normal: Vec<T> = (0..dim)
+            .map(|column| {
+                let minor: Vec<Vec<T>> = edges
+                    .iter()
+                    .map(|e| {
+                        e.iter()
+                            .enumerate()
+                            .filter(|(k, _)| *k != column)
+                            .map(|(_, v)| *v)
+                            .collect()
+                    })
+                    .collect();
+                let cofactor = if minor.is_empty() {
+                    T::one()
+                } else {
+                    determinant(&minor)
+                };
+                if column % 2 == 1 {
+                    cofactor
+                } else {
+                    -cofactor
+                }
+            })
+            .collect();
+        Hyperplane::from_point_normal(&points[0], &Point::new_from_vec(&normal))
+    }
+
+    /// The unit normal.
+    pub fn normal(&self) -> &Point<T> {
+        &self.normal
+    }
+
+    /// The offset: the signed distance of the hyperplane from the origin, along the normal.
+    pub fn offset(&self) -> T {
+        self.offset
+    }
+
+    /// The dimension of the space of the hyperplane.
+    pub fn get_size(&self) -> usize {
+        self.normal.get_size()
+    }
+
+    /// The same hyperplane, with the opposite normal.
+    pub fn flipped(&self) -> Hyperplane<T> {
+        let values: Vec<T> = self.normal.get_vector().iter().map(|v| -*v).collect();
+        Hyperplane {
+            normal: Point::new_from_vec(&values),
+            offset: -self.offset,
+        }
+    }
+
+    /// The distance of the point from the hyperplane: positive in the direction of the normal.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the hyperplane are not equal.
+    pub fn signed_distance(&self, point: &Point<T>) -> T {
+        self.normal.dot(point) - self.offset
+    }
+
+    /// The point moved along the normal by the distance.
+    fn shift(&self, point: &Point<T>, distance: T) -> Point<T> {
+        let values: Vec<T> = point
+            .get_vector()
+            .iter()
+            .zip(self.normal.get_vector())
+            .map(|(p, n)| *p + *n * distance)
+            .collect();
+        Point::new_from_vec(&values)
+    }
+
+    /// The orthogonal projection of the point onto the hyperplane.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the hyperplane are not equal.
+    pub fn project(&self, point: &Point<T>) -> Point<T> {
+        self.shift(point, -self.signed_distance(point))
+    }
+
+    /// The mirror image of the point in the hyperplane.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the hyperplane are not equal.
+    pub fn reflect(&self, point: &Point<T>) -> Point<T> {
+        let two = T::one() + T::one();
+        self.shift(point, -two * self.signed_distance(point))
+    }
+
+    /// The side of the hyperplane the point is on. Points closer than the tolerance are on it.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the hyperplane are not equal.
+    pub fn side(&self, point: &Point<T>, tolerance: T) -> Side {
+        let distance = self.signed_distance(point);
+        if distance > tolerance {
+            Side::Positive
+        } else if distance < -tolerance {
+            Side::Negative
+        } else {
+            Side::On
+        }
+    }
+
+    /// The parameter `t` where the line `origin + t * direction` crosses the hyperplane. `None`
+    /// if the line is parallel to the hyperplane (including lines on it).
+    /// # Panic
+    /// This function will panic if the dimensions of the points and the hyperplane are not equal.
+    pub fn line_parameter(&self, origin: &Point<T>, direction: &Point<T>) -> Option<T> {
+        let speed = self.normal.dot(direction);
+        let t = -self.signed_distance(origin) / speed;
+        if speed.is_zero() || !t.is_finite() {
+            None
+        } else {
+            Some(t)
+        }
+    }
+
+    /// The point where the line `origin + t * direction` crosses the hyperplane. `None` if the
+    /// line is parallel to the hyperplane (including lines on it).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::hyperplane::Hyperplane;
+    /// use multi_dim_point::Point;
+    /// let plane = Hyperplane::new(&Point::new_from_vec(&vec![0.0, 0.0, 1.0]), 2.0).unwrap();
+    /// let origin = Point::new_from_vec(&vec![1.0, 1.0, 0.0]);
+    /// let up = Point::new_from_vec(&vec![1.0, 0.0, 1.0]);
+    /// let sideways = Point::new_from_vec(&vec![1.0, 0.0, 0.0]);
+    /// assert_eq!(plane.intersect_line(&origin, &up).unwrap().get_vector(), &vec![3.0, 1.0, 2.0]);
+    /// assert!(plane.intersect_line(&origin, &sideways).is_none());
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points and the hyperplane are not equal.
+    pub fn intersect_line(&self, origin: &Point<T>, direction: &Point<T>) -> Option<Point<T>> {
+        let t = self.line_parameter(origin, direction)?;
+        let values: Vec<T> = origin
+            .get_vector()
+            .iter()
+            .zip(direction.get_vector())
+            .map(|(o, d)| *o + *d * t)
+            .collect();
+        Some(Point::new_from_vec(&values))
+    }
+
+    /// The point where the segment between the points crosses the hyperplane, or `None` if it
+    /// does not (or lies on it).
+    /// # Panic
+    /// This function will panic if the dimensions of the points and the hyperplane are not equal.
+    pub fn intersect_segment(&self, start: &Point<T>, end: &Point<T>) -> Option<Point<T>> {
+        let direction: Vec<T> = end
+            .get_vector()
+            .iter()
+            .zip(start.get_vector())
+            .map(|(a, b)| *a - *b)
+            .collect();
+        let t = self.line_parameter(start, &Point::new_from_vec(&direction))?;
+        if t < T::zero() || t > T::one() {
+            return None;
+        }
+        let values: Vec<T> = start
+            .get_vector()
+            .iter()
+            .zip(&direction)
+            .map(|(o, d)| *o + *d * t)
+            .collect();
+        Some(Point::new_from_vec(&values))
+    }
+}
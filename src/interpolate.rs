@@ -0,0 +1,233 @@
+//! Interpolation between points: linear, spherical and Bézier curves.
+use crate::Point;
+use num::Float;
+use std::cmp::Ordering;
+
+impl<T> Point<T>
+where
+    T: Float,
+{
+    /// Linear interpolation between the points: `t = 0` returns this point, `t = 1` returns
+    /// the other point (values of `t` outside `[0, 1]` extrapolate).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![0.0, 10.0]);
+    /// let p2: Point<f64> = Point::new_from_vec(&vec![4.0, 20.0]);
+    /// assert_eq!(p1.lerp(&p2, 0.25).get_vector(), &vec![1.0, 12.5]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn lerp(&self, other: &Point<T>, t: T) -> Point<T> {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a + (*b - *a) * t))
+    }
+
+    /// Spherical linear interpolation between the points, as direction vectors: the direction
+    /// rotates from this point's direction to the other's in constant angular speed, and the
+    /// length is interpolated linearly.
+    ///
+    /// When one of the points is zero or not finite (or the points are 1-dimensional), there is
+    /// no direction to rotate and the points are interpolated linearly. When the directions are
+    /// opposite, the rotation is done in a (deterministically chosen) plane that contains them.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 0.0]);
+    /// let p2: Point<f64> = Point::new_from_vec(&vec![0.0, 3.0]);
+    /// let half = std::f64::consts::FRAC_1_SQRT_2 * 2.0;
+    /// assert!(p1.slerp(&p2, 0.5).close(&Point::new_from_vec(&vec![half, half]), 1e-12));
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn slerp(&self, other: &Point<T>, t: T) -> Point<T>
+    where
+        T: Default,
+    {
+        if self.get_size() != other.get_size() {
+            panic!("dimensions are not equal");
+        }
+        let (norm_a, norm_b) = (self.norm(), other.norm());
+        if norm_a.is_zero() || norm_b.is_zero() || !norm_a.is_finite() || !norm_b.is_finite() {
+            return self.lerp(other, t);
+        }
+        if self.get_size() < 2 {
+            // there is no plane to rotate in.
+            return self.lerp(other, t);
+        }
+        let a = self / &norm_a;
+        let b = other / &norm_b;
+        let cos = a.dot(&b).max(-T::one()).min(T::one());
+        let length = norm_a + (norm_b - norm_a) * t;
+        let eps = T::epsilon().sqrt();
+        let direction = if T::one() - cos < eps {
+            // (almost) the same direction.
+            let dir = a.lerp(&b, t);
+            &dir / &dir.norm()
+        } else if T::one() + cos < eps {
+            // opposite directions, rotate through a perpendicular direction.
+            let u = perpendicular(&a);
+            let angle = T::from(std::f64::consts::PI).unwrap() * t;
+            &(&a * &angle.cos()) + &(&u * &angle.sin())
+        } else {
+            let omega = cos.acos();
+            let sin = omega.sin();
+            &(&a * &(((T::one() - t) * omega).sin() / sin)) + &(&b * &((t * omega).sin() / sin))
+        };
+        &direction * &length
+    }
+}
+
+/// A unit vector perpendicular to the unit vector `a` (which has at least 2 dimensions).
+fn perpendicular<T: Float + Default>(a: &Point<T>) -> Point<T> {
+    // start from the axis where `a` is the smallest, and remove the component along `a`.
+    let values = a.get_vector();
+    let axis = (0..values.len())
+        .min_by(|i, j| {
+            values[*i]
+                .abs()
+                .partial_cmp(&values[*j].abs())
+                .unwrap_or(Ordering::Equal)
+        })
+        .unwrap();
+    let mut u = a * &-values[axis];
+    let value = *u.get_value(axis + 1) + T::one();
+    u.set_value(axis + 1, &value);
+    &u / &u.norm()
+}
+
+/// Bézier curve of any degree, defined by its control points.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::interpolate::Bezier;
+/// use multi_dim_point::Point;
+/// let curve = Bezier::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 2.0]),
+///     Point::new_from_vec(&vec![2.0, 0.0]),
+/// ]);
+/// assert_eq!(curve.degree(), 2);
+/// assert_eq!(curve.eval(0.5).get_vector(), &vec![1.0, 1.0]);
+/// assert_eq!(curve.tangent(0.5).get_vector(), &vec![2.0, 0.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bezier<T> {
+    control_points: Vec<Point<T>>,
+}
+
+impl<T> Bezier<T>
+where
+    T: Float,
+{
+    /// Creates a curve from its control points (the degree of the curve is the number of
+    /// control points minus 1).
+    /// # Panic
+    /// This function will panic when there are no control points, or when their dimensions are
+    /// not equal.
+    pub fn new(control_points: Vec<Point<T>>) -> Bezier<T> {
+        if control_points.is_empty() {
+            panic!("a Bézier curve needs at least one control point");
+        }
+        let dim = control_points[0].get_size();
+        if control_points.iter().any(|p| p.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        Bezier { control_points }
+    }
+
+    /// Return the control points.
+    pub fn control_points(&self) -> &[Point<T>] {
+        &self.control_points
+    }
+
+    /// Return the degree of the curve.
+    pub fn degree(&self) -> usize {
+        self.control_points.len() - 1
+    }
+
+    /// Return the dimension of the curve's points.
+    pub fn get_size(&self) -> usize {
+        self.control_points[0].get_size()
+    }
+
+    /// All the levels of de Casteljau's algorithm: the first is the control points, the last
+    /// has a single point (the point of the curve).
+    fn de_casteljau(&self, t: T) -> Vec<Vec<Point<T>>> {
+        let mut levels = vec![self.control_points.clone()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|pair| pair[0].lerp(&pair[1], t))
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    /// Evaluate the curve in `t` (the curve starts in `t = 0` and ends in `t = 1`), by de
+    /// Casteljau's algorithm.
+    pub fn eval(&self, t: T) -> Point<T> {
+        self.de_casteljau(t).pop().unwrap().pop().unwrap()
+    }
+
+    /// The derivative of the curve (by `t`), which is a curve of one less degree. The
+    /// derivative of a curve of degree 0 is a zero curve of degree 0.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::interpolate::Bezier;
+    /// use multi_dim_point::Point;
+    /// let line = Bezier::new(vec![
+    ///     Point::new_from_vec(&vec![1.0, 1.0]),
+    ///     Point::new_from_vec(&vec![3.0, 0.0]),
+    /// ]);
+    /// let derivative = line.derivative();
+    /// assert_eq!(derivative.degree(), 0);
+    /// assert_eq!(derivative.eval(0.3).get_vector(), &vec![2.0, -1.0]);
+    /// ```
+    pub fn derivative(&self) -> Bezier<T> {
+        if self.degree() == 0 {
+            let zero = vec![T::zero(); self.get_size()];
+            return Bezier::new(vec![Point::new_from_vec(&zero)]);
+        }
+        let n = T::from(self.degree()).unwrap();
+        let control_points = self
+            .control_points
+            .windows(2)
+            .map(|pair| Point::new_from_vec(&pair[1].apply_func(&pair[0], &|b, a| (*b - *a) * n)))
+            .collect();
+        Bezier::new(control_points)
+    }
+
+    /// The tangent vector (first derivative) of the curve in `t`.
+    pub fn tangent(&self, t: T) -> Point<T> {
+        self.derivative().eval(t)
+    }
+
+    /// Split the curve in `t` into two curves of the same degree: the first is the part of the
+    /// curve in `[0, t]`, and the second is the part in `[t, 1]`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::interpolate::Bezier;
+    /// use multi_dim_point::Point;
+    /// let curve = Bezier::new(vec![
+    ///     Point::new_from_vec(&vec![0.0, 0.0]),
+    ///     Point::new_from_vec(&vec![1.0, 2.0]),
+    ///     Point::new_from_vec(&vec![2.0, 0.0]),
+    /// ]);
+    /// let (left, right) = curve.subdivide(0.5);
+    /// assert!(left.eval(0.5).close(&curve.eval(0.25), 1e-12));
+    /// assert!(right.eval(0.5).close(&curve.eval(0.75), 1e-12));
+    /// ```
+    pub fn subdivide(&self, t: T) -> (Bezier<T>, Bezier<T>) {
+        let levels = self.de_casteljau(t);
+        let left = levels.iter().map(|level| level[0].clone()).collect();
+        let right = levels
+            .iter()
+            .rev()
+            .map(|level| level[level.len() - 1].clone())
+            .collect();
+        (Bezier::new(left), Bezier::new(right))
+    }
+}
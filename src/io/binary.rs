@@ -0,0 +1,583 @@
+//! Compact binary format for point sets.
+//!
+//! All the numbers are little-endian. A file starts with a 24 bytes header:
+//!
+//! | offset | size | content                                         |
+//! |--------|------|-------------------------------------------------|
+//! | 0      | 4    | magic, `b"MDPT"`                                |
+//! | 4      | 2    | format version (`u16`, currently 1)             |
+//! | 6      | 1    | scalar type (see [`ScalarType`])                |
+//! | 7      | 1    | reserved (0)                                    |
+//! | 8      | 4    | dimension of the points (`u32`)                 |
+//! | 12     | 8    | number of points (`u64`)                        |
+//! | 20     | 4    | reserved (0), so the values are 8-byte aligned  |
+//!
+//! The header is followed by the values of the points, point after point (`count * dimension`
+//! values).
+//!
+//! Points are written with [`BinaryWriter`], and can be read with [`BinaryReader`] or, with the
+//! `mmap` feature, with [`MmapPoints`] that exposes the points as slices without loading the
+//! file into memory.
+//!
+//! # Example
+//! ```
+//! use multi_dim_point::io::binary::{BinaryReader, BinaryWriter};
+//! use multi_dim_point::Point;
+//! use std::io::Cursor;
+//!
+//! let mut writer = BinaryWriter::<f64, _>::new(Cursor::new(Vec::new()), 2).unwrap();
+//! writer.write(&Point::new_from_vec(&vec![1.0, 2.0])).unwrap();
+//! writer.write_slice(&[3.0, 4.0]).unwrap();
+//! let bytes = writer.finish().unwrap().into_inner();
+//!
+//! let reader = BinaryReader::<f64, _>::new(bytes.as_slice()).unwrap();
+//! assert_eq!(reader.len(), 2);
+//! let points = reader.collect::<Result<Vec<_>, _>>().unwrap();
+//! assert_eq!(points[1].get_vector(), &vec![3.0, 4.0]);
+//! ```
+use crate::Point;
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+
+/// The magic bytes in the start of the file.
+pub const MAGIC: [u8; 4] = *b"MDPT";
+/// The current version of the format.
+pub const VERSION: u16 = 1;
+/// The size of the header, in bytes.
+pub const HEADER_LEN: usize = 24;
+
+/// The type of the values stored in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarType {
+    U8 = 1,
+    U16 = 2,
+    U32 = 3,
+    U64 = 4,
+    I8 = 5,
+    I16 = 6,
+    I32 = 7,
+    I64 = 8,
+    F32 = 9,
+    F64 = 10,
+}
+
+impl ScalarType {
+    fn from_code(code: u8) -> Option<ScalarType> {
+        use ScalarType::*;
+        [U8, U16, U32, U64, I8, I16, I32, I64, F32, F64]
+            .iter()
+            .copied()
+            .find(|t| *t as u8 == code)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A value type that can be stored in the binary format. Implemented for the primitive
+/// integer and floating point types (every bit pattern of them is a valid value, which makes the
+/// zero-copy reading possible).
+pub trait BinaryScalar: Copy + private::Sealed {
+    /// The scalar type written in the header.
+    const SCALAR_TYPE: ScalarType;
+    /// The size of one value, in bytes.
+    const SIZE: usize;
+    /// Append the little-endian representation of the value.
+    fn write_le(&self, out: &mut Vec<u8>);
+    /// Read a value from its little-endian representation (`bytes.len() == Self::SIZE`).
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_binary_scalar {
+    ($($t:ty => $scalar:ident),*) => ($(
+        impl private::Sealed for $t {}
+        impl BinaryScalar for $t {
+            const SCALAR_TYPE: ScalarType = ScalarType::$scalar;
+            const SIZE: usize = std::mem::size_of::<$t>();
+            fn write_le(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+            fn read_le(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().unwrap())
+            }
+        }
+    )*)
+}
+
+impl_binary_scalar! {
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64,
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64,
+    f32 => F32, f64 => F64
+}
+
+/// Error returned when reading or writing the binary format fails.
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(io::Error),
+    /// The file doesn't start with [`MAGIC`].
+    BadMagic,
+    /// The file was written with an unknown version of the format.
+    UnsupportedVersion(u16),
+    /// The values in the file are not of the requested type.
+    ScalarTypeMismatch {
+        expected: ScalarType,
+        found: u8,
+    },
+    /// A point with a wrong dimension was written.
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The file is shorter than the header says.
+    Truncated {
+        expected: u64,
+        found: u64,
+    },
+    /// The values can't be used in place on this platform (big-endian or unaligned data).
+    Unsupported,
+    /// The header describes more data than can be addressed, or a dimension that doesn't fit
+    /// in it.
+    TooLarge,
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryError::Io(e) => e.fmt(f),
+            BinaryError::BadMagic => write!(f, "not a point set file (bad magic)"),
+            BinaryError::UnsupportedVersion(v) => write!(f, "unsupported format version {}", v),
+            BinaryError::ScalarTypeMismatch { expected, found } => write!(
+                f,
+                "expected values of type {:?}, found type code {}",
+                expected, found
+            ),
+            BinaryError::DimensionMismatch { expected, found } => write!(
+                f,
+                "expected a point with {} dimensions, found {}",
+                expected, found
+            ),
+            BinaryError::Truncated { expected, found } => write!(
+                f,
+                "file is truncated (expected {} bytes, found {})",
+                expected, found
+            ),
+            BinaryError::Unsupported => {
+                write!(f, "values can't be used in place on this platform")
+            }
+            BinaryError::TooLarge => write!(f, "the header describes too much data"),
+        }
+    }
+}
+
+impl Error for BinaryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BinaryError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BinaryError {
+    fn from(e: io::Error) -> Self {
+        BinaryError::Io(e)
+    }
+}
+
+/// The header of a point set file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub scalar_type: ScalarType,
+    pub dimension: usize,
+    pub count: u64,
+}
+
+impl Header {
+    /// Encode the header.
+    /// # Panic
+    /// This function will panic if the dimension doesn't fit in a `u32`.
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let dimension = match u32::try_from(self.dimension) {
+            Ok(dimension) => dimension,
+            Err(_) => panic!("the dimension {} doesn't fit in a header", self.dimension),
+        };
+        let mut bytes = [0; HEADER_LEN];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..6].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[6] = self.scalar_type as u8;
+        bytes[8..12].copy_from_slice(&dimension.to_le_bytes());
+        bytes[12..20].copy_from_slice(&self.count.to_le_bytes());
+        bytes
+    }
+
+    /// Decode a header, and check that it holds values of type `T`.
+    pub fn from_bytes<T: BinaryScalar>(bytes: &[u8; HEADER_LEN]) -> Result<Header, BinaryError> {
+        if bytes[0..4] != MAGIC {
+            return Err(BinaryError::BadMagic);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(BinaryError::UnsupportedVersion(version));
+        }
+        let scalar_type = match ScalarType::from_code(bytes[6]) {
+            Some(t) if t == T::SCALAR_TYPE => t,
+            _ => {
+                return Err(BinaryError::ScalarTypeMismatch {
+                    expected: T::SCALAR_TYPE,
+                    found: bytes[6],
+                })
+            }
+        };
+        Ok(Header {
+            scalar_type,
+            dimension: u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize,
+            count: u64::from_le_bytes(bytes[12..20].try_into().unwrap()),
+        })
+    }
+
+    /// The size of the whole file (header and values), in bytes.
+    ///
+    /// # Errors
+    /// [`BinaryError::TooLarge`] is returned when the size doesn't fit in a `u64`.
+    pub fn file_len(&self) -> Result<u64, BinaryError> {
+        self.count
+            .checked_mul(self.dimension as u64)
+            .and_then(|values| values.checked_mul(self.scalar_size() as u64))
+            .and_then(|bytes| bytes.checked_add(HEADER_LEN as u64))
+            .ok_or(BinaryError::TooLarge)
+    }
+
+    fn scalar_size(&self) -> usize {
+        match self.scalar_type {
+            ScalarType::U8 | ScalarType::I8 => 1,
+            ScalarType::U16 | ScalarType::I16 => 2,
+            ScalarType::U32 | ScalarType::I32 | ScalarType::F32 => 4,
+            ScalarType::U64 | ScalarType::I64 | ScalarType::F64 => 8,
+        }
+    }
+}
+
+/// Streaming writer of point sets. The number of points is written to the header by
+/// [`BinaryWriter::finish`], so the points don't need to be known in advance. A writer dropped
+/// without calling `finish` leaves a count of 0 in the header, so the points written are lost.
+pub struct BinaryWriter<T, W>
+where
+    T: BinaryScalar,
+    W: Write + Seek,
+{
+    writer: W,
+    start: u64,
+    dimension: usize,
+    count: u64,
+    buffer: Vec<u8>,
+    _scalar: PhantomData<T>,
+}
+
+impl<T, W> BinaryWriter<T, W>
+where
+    T: BinaryScalar,
+    W: Write + Seek,
+{
+    /// Create a writer of points with the given dimension, and write a temporary header.
+    ///
+    /// # Errors
+    /// [`BinaryError::TooLarge`] is returned when the dimension doesn't fit in a `u32`.
+    pub fn new(mut writer: W, dimension: usize) -> Result<Self, BinaryError> {
+        if u32::try_from(dimension).is_err() {
+            return Err(BinaryError::TooLarge);
+        }
+        let header = Header {
+            scalar_type: T::SCALAR_TYPE,
+            dimension,
+            count: 0,
+        };
+        let start = writer.stream_position()?;
+        writer.write_all(&header.to_bytes())?;
+        Ok(BinaryWriter {
+            writer,
+            start,
+            dimension,
+            count: 0,
+            buffer: Vec::new(),
+            _scalar: PhantomData,
+        })
+    }
+
+    /// Write the values of one point.
+    pub fn write_slice(&mut self, values: &[T]) -> Result<(), BinaryError> {
+        if values.len() != self.dimension {
+            return Err(BinaryError::DimensionMismatch {
+                expected: self.dimension,
+                found: values.len(),
+            });
+        }
+        self.buffer.clear();
+        for value in values {
+            value.write_le(&mut self.buffer);
+        }
+        self.writer.write_all(&self.buffer)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write one point.
+    pub fn write(&mut self, point: &Point<T>) -> Result<(), BinaryError> {
+        self.write_slice(point.get_vector())
+    }
+
+    /// Write the final header, and return the underlying writer.
+    pub fn finish(mut self) -> Result<W, BinaryError> {
+        let header = Header {
+            scalar_type: T::SCALAR_TYPE,
+            dimension: self.dimension,
+            count: self.count,
+        };
+        let end = self.writer.stream_position()?;
+        self.writer.seek(SeekFrom::Start(self.start))?;
+        self.writer.write_all(&header.to_bytes())?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Streaming reader of point sets, iterating over the points of the file.
+pub struct BinaryReader<T, R>
+where
+    T: BinaryScalar,
+    R: Read,
+{
+    reader: R,
+    header: Header,
+    read: u64,
+    buffer: Vec<u8>,
+    _scalar: PhantomData<T>,
+}
+
+impl<T, R> BinaryReader<T, R>
+where
+    T: BinaryScalar,
+    R: Read,
+{
+    /// Read the header, and check that the file holds values of type `T`.
+    pub fn new(mut reader: R) -> Result<Self, BinaryError> {
+        let mut bytes = [0; HEADER_LEN];
+        reader.read_exact(&mut bytes)?;
+        let header = Header::from_bytes::<T>(&bytes)?;
+        header.file_len()?;
+        Ok(BinaryReader {
+            reader,
+            header,
+            read: 0,
+            // grown while reading, so a bad header can't allocate more than the data.
+            buffer: Vec::new(),
+            _scalar: PhantomData,
+        })
+    }
+
+    /// The header of the file.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The number of points in the file.
+    pub fn len(&self) -> u64 {
+        self.header.count
+    }
+
+    /// Return `true` when the file holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, R> Iterator for BinaryReader<T, R>
+where
+    T: BinaryScalar,
+    R: Read,
+{
+    type Item = Result<Point<T>, BinaryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.read == self.header.count {
+            return None;
+        }
+        self.read += 1;
+        let point_len = (self.header.dimension * T::SIZE) as u64;
+        self.buffer.clear();
+        let result = (&mut self.reader)
+            .take(point_len)
+            .read_to_end(&mut self.buffer)
+            .and_then(|n| {
+                if n as u64 == point_len {
+                    Ok(())
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ))
+                }
+            });
+        if let Err(e) = result {
+            self.read = self.header.count;
+            return Some(Err(e.into()));
+        }
+        let values: Vec<T> = self.buffer.chunks_exact(T::SIZE).map(T::read_le).collect();
+        Some(Ok(Point::new_from_vec(&values)))
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapPoints;
+
+#[cfg(feature = "mmap")]
+mod mmap {
+    use super::{BinaryError, BinaryScalar, Header, HEADER_LEN};
+    use crate::Point;
+    use memmap2::Mmap;
+    use std::convert::{TryFrom, TryInto};
+    use std::fs::File;
+    use std::marker::PhantomData;
+    use std::path::Path;
+
+    /// Memory-mapped point set file (requires the `mmap` feature). The points are exposed as
+    /// slices into the mapped file, without copying.
+    ///
+    /// # Example
+    /// ```
+    /// use multi_dim_point::io::binary::{BinaryWriter, MmapPoints};
+    /// use std::fs::File;
+    ///
+    /// let path = std::env::temp_dir().join("multi_dim_point_mmap_doc.mdpt");
+    /// let mut writer = BinaryWriter::<i32, _>::new(File::create(&path).unwrap(), 3).unwrap();
+    /// writer.write_slice(&[1, 2, 3]).unwrap();
+    /// writer.write_slice(&[4, 5, 6]).unwrap();
+    /// writer.finish().unwrap();
+    ///
+    /// let points = unsafe { MmapPoints::<i32>::open(&path) }.unwrap();
+    /// assert_eq!(points.len(), 2);
+    /// assert_eq!(points.get(1), Some(&[4, 5, 6][..]));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub struct MmapPoints<T>
+    where
+        T: BinaryScalar,
+    {
+        mmap: Mmap,
+        header: Header,
+        len: usize,
+        _scalar: PhantomData<T>,
+    }
+
+    impl<T> MmapPoints<T>
+    where
+        T: BinaryScalar,
+    {
+        /// Map a point set file into memory.
+        ///
+        /// # Safety
+        /// The file must not be modified (by this or another process) while it is mapped.
+        ///
+        /// # Errors
+        /// Beside the header errors, [`BinaryError::Unsupported`] is returned on big-endian
+        /// platforms, where the values can't be used in place.
+        pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, BinaryError> {
+            if cfg!(target_endian = "big") {
+                return Err(BinaryError::Unsupported);
+            }
+            let file = File::open(path)?;
+            let mmap = Mmap::map(&file)?;
+            if mmap.len() < HEADER_LEN {
+                return Err(BinaryError::Truncated {
+                    expected: HEADER_LEN as u64,
+                    found: mmap.len() as u64,
+                });
+            }
+            let header = Header::from_bytes::<T>(mmap[..HEADER_LEN].try_into().unwrap())?;
+            let file_len = header.file_len()?;
+            if (mmap.len() as u64) < file_len {
+                return Err(BinaryError::Truncated {
+                    expected: file_len,
+                    found: mmap.len() as u64,
+                });
+            }
+            let len = usize::try_from(header.count).map_err(|_| BinaryError::TooLarge)?;
+            len.checked_mul(header.dimension)
+                .ok_or(BinaryError::TooLarge)?;
+            if mmap[HEADER_LEN..]
+                .as_ptr()
+                .align_offset(std::mem::align_of::<T>())
+                != 0
+            {
+                return Err(BinaryError::Unsupported);
+            }
+            Ok(MmapPoints {
+                mmap,
+                header,
+                len,
+                _scalar: PhantomData,
+            })
+        }
+
+        /// The header of the file.
+        pub fn header(&self) -> &Header {
+            &self.header
+        }
+
+        /// The dimension of the points.
+        pub fn dimension(&self) -> usize {
+            self.header.dimension
+        }
+
+        /// The number of points in the file.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Return `true` when the file holds no points.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// All the values in the file, point after point.
+        pub fn as_slice(&self) -> &[T] {
+            let data = &self.mmap[HEADER_LEN..];
+            let values = self.len() * self.dimension();
+            assert!(values * std::mem::size_of::<T>() <= data.len());
+            // The length (without overflow) and the alignment were checked in `open`, the
+            // platform is little-endian and every bit pattern is a valid `T` (`BinaryScalar` is
+            // sealed).
+            unsafe { std::slice::from_raw_parts(data.as_ptr() as *const T, values) }
+        }
+
+        /// The values of the point in the given index (starts from 0), or `None` when the
+        /// index is out of range.
+        pub fn get(&self, index: usize) -> Option<&[T]> {
+            if index >= self.len() {
+                return None;
+            }
+            let dim = self.dimension();
+            Some(&self.as_slice()[index * dim..(index + 1) * dim])
+        }
+
+        /// Copy the point in the given index into an owned point.
+        pub fn point(&self, index: usize) -> Option<Point<T>> {
+            self.get(index)
+                .map(|values| Point::new_from_vec(&values.to_vec()))
+        }
+
+        /// Iterate over the points, as slices.
+        pub fn iter(&self) -> impl Iterator<Item = &[T]> + '_ {
+            // `chunks_exact` panics on zero size chunks.
+            let dim = self.dimension().max(1);
+            self.as_slice()
+                .chunks_exact(dim)
+                .chain(std::iter::repeat(&[][..]))
+                .take(self.len())
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! CSV import and export of point sets (requires the `csv` feature).
+//!
+//! Each row of the file is one point. By default every column is a coordinate, but the
+//! coordinates can be selected with [`CsvOptions::columns`], and other columns can be kept as a
+//! payload (e.g. a label) with [`CsvOptions::payload_columns`].
+//!
+//! # Example
+//! ```
+//! use multi_dim_point::io::csv::{read_points, write_points, CsvOptions};
+//! use multi_dim_point::Point;
+//!
+//! let data = "x,y,name\n1,2,a\n3,4,b\n";
+//! let options = CsvOptions::new().columns(&["x", "y"]);
+//! let points: Vec<Point<i32>> = read_points(data.as_bytes(), &options).unwrap();
+//! assert_eq!(points[1].get_vector(), &vec![3, 4]);
+//!
+//! let mut out = Vec::new();
+//! write_points(&mut out, &points, Some(&["x", "y"])).unwrap();
+//! assert_eq!(String::from_utf8(out).unwrap(), "x,y\n1,2\n3,4\n");
+//! ```
+use crate::Point;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A point, with the values of the payload columns of its row.
+pub type PointWithPayload<T> = (Point<T>, Vec<String>);
+
+/// A column of the CSV file, by its (0-based) position or by its header name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        Column::Name(name)
+    }
+}
+
+/// Options for reading points from CSV.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    has_headers: bool,
+    delimiter: u8,
+    columns: Option<Vec<Column>>,
+    payload_columns: Vec<Column>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::new()
+    }
+}
+
+impl CsvOptions {
+    /// Default options: the first row is a header, the delimiter is a comma, and every column
+    /// is a coordinate.
+    pub fn new() -> CsvOptions {
+        CsvOptions {
+            has_headers: true,
+            delimiter: b',',
+            columns: None,
+            payload_columns: Vec::new(),
+        }
+    }
+
+    /// Set whether the first row is a header (and not a point).
+    pub fn has_headers(mut self, yes: bool) -> CsvOptions {
+        self.has_headers = yes;
+        self
+    }
+
+    /// Set the field delimiter.
+    pub fn delimiter(mut self, delimiter: u8) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Select the coordinate columns, in the order of the point's dimensions.
+    pub fn columns<C>(mut self, columns: &[C]) -> CsvOptions
+    where
+        C: Clone + Into<Column>,
+    {
+        self.columns = Some(columns.iter().cloned().map(Into::into).collect());
+        self
+    }
+
+    /// Select columns that are kept as a payload of each point (see [`read_points_with_payload`]).
+    /// Payload columns are never used as coordinates.
+    pub fn payload_columns<C>(mut self, columns: &[C]) -> CsvOptions
+    where
+        C: Clone + Into<Column>,
+    {
+        self.payload_columns = columns.iter().cloned().map(Into::into).collect();
+        self
+    }
+}
+
+/// Error returned when reading or writing CSV fails. Line numbers start from 1 and count the
+/// header row.
+#[derive(Debug)]
+pub enum CsvError {
+    /// Error from the underlying CSV reader/writer (including I/O errors).
+    Csv(::csv::Error),
+    /// A column was selected by name, but there is no such header.
+    UnknownColumn(String),
+    /// A row doesn't have the expected number of fields.
+    InconsistentDimension {
+        line: u64,
+        expected: usize,
+        found: usize,
+    },
+    /// A field could not be parsed as a coordinate.
+    InvalidValue {
+        line: u64,
+        column: usize,
+        value: String,
+    },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Csv(e) => e.fmt(f),
+            CsvError::UnknownColumn(name) => write!(f, "unknown column {:?}", name),
+            CsvError::InconsistentDimension {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} fields, found {}",
+                line, expected, found
+            ),
+            CsvError::InvalidValue {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}: invalid value {:?} in column {}",
+                line, value, column
+            ),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::Csv(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<::csv::Error> for CsvError {
+    fn from(e: ::csv::Error) -> Self {
+        CsvError::Csv(e)
+    }
+}
+
+fn resolve(column: &Column, headers: Option<&::csv::StringRecord>) -> Result<usize, CsvError> {
+    match column {
+        Column::Index(i) => Ok(*i),
+        Column::Name(name) => headers
+            .and_then(|h| h.iter().position(|field| field.trim() == name))
+            .ok_or_else(|| CsvError::UnknownColumn(name.clone())),
+    }
+}
+
+/// Read points from CSV data. Each point is kept with the values of the payload columns.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::io::csv::{read_points_with_payload, CsvOptions};
+/// use multi_dim_point::Point;
+///
+/// let data = "1.5;2;first\n3;4;second\n";
+/// let options = CsvOptions::new()
+///     .has_headers(false)
+///     .delimiter(b';')
+///     .columns(&[0, 1])
+///     .payload_columns(&[2]);
+/// let points = read_points_with_payload::<f64, _>(data.as_bytes(), &options).unwrap();
+/// assert_eq!(points[0].0.get_vector(), &vec![1.5, 2.0]);
+/// assert_eq!(points[1].1, vec!["second".to_string()]);
+/// ```
+pub fn read_points_with_payload<T, R>(
+    reader: R,
+    options: &CsvOptions,
+) -> Result<Vec<PointWithPayload<T>>, CsvError>
+where
+    T: Clone + FromStr,
+    R: Read,
+{
+    let mut reader = ::csv::ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let headers = if options.has_headers {
+        Some(reader.headers()?.clone())
+    } else {
+        None
+    };
+    let payload = options
+        .payload_columns
+        .iter()
+        .map(|c| resolve(c, headers.as_ref()))
+        .collect::<Result<Vec<usize>, CsvError>>()?;
+    let columns = match &options.columns {
+        Some(columns) => Some(
+            columns
+                .iter()
+                .map(|c| resolve(c, headers.as_ref()))
+                .collect::<Result<Vec<usize>, CsvError>>()?,
+        ),
+        None => None,
+    };
+    // every row must have the same number of fields as the first one (or the header).
+    let mut expected = headers.as_ref().map(|h| h.len());
+    let mut points = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        let width = *expected.get_or_insert(record.len());
+        let needed = columns
+            .iter()
+            .flatten()
+            .chain(payload.iter())
+            .map(|i| i + 1)
+            .max()
+            .unwrap_or(0);
+        if record.len() != width || record.len() < needed {
+            return Err(CsvError::InconsistentDimension {
+                line,
+                expected: width.max(needed),
+                found: record.len(),
+            });
+        }
+        let coordinates: Vec<usize> = match &columns {
+            Some(columns) => columns.clone(),
+            None => (0..record.len()).filter(|i| !payload.contains(i)).collect(),
+        };
+        let mut values = Vec::with_capacity(coordinates.len());
+        for column in coordinates {
+            let field = record[column].trim();
+            let value = field.parse().map_err(|_| CsvError::InvalidValue {
+                line,
+                column,
+                value: field.to_string(),
+            })?;
+            values.push(value);
+        }
+        let labels = payload.iter().map(|i| record[*i].to_string()).collect();
+        points.push((Point::new_from_vec(&values), labels));
+    }
+    Ok(points)
+}
+
+/// Read points from CSV data.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::io::csv::{read_points, CsvError, CsvOptions};
+/// use multi_dim_point::Point;
+///
+/// let data = "1,2\n3,4\n5\n";
+/// let options = CsvOptions::new().has_headers(false);
+/// let err = read_points::<i32, _>(data.as_bytes(), &options).unwrap_err();
+/// assert!(matches!(err, CsvError::InconsistentDimension { line: 3, .. }));
+/// ```
+pub fn read_points<T, R>(reader: R, options: &CsvOptions) -> Result<Vec<Point<T>>, CsvError>
+where
+    T: Clone + FromStr,
+    R: Read,
+{
+    Ok(read_points_with_payload(reader, options)?
+        .into_iter()
+        .map(|(point, _)| point)
+        .collect())
+}
+
+/// Read points from a CSV file.
+pub fn read_points_from_path<T, P>(path: P, options: &CsvOptions) -> Result<Vec<Point<T>>, CsvError>
+where
+    T: Clone + FromStr,
+    P: AsRef<Path>,
+{
+    let file = File::open(path).map_err(|e| CsvError::Csv(e.into()))?;
+    read_points(file, options)
+}
+
+/// Write points as CSV, one point per row, with an optional header row.
+pub fn write_points<T, W>(
+    writer: W,
+    points: &[Point<T>],
+    headers: Option<&[&str]>,
+) -> Result<(), CsvError>
+where
+    T: Clone + fmt::Display,
+    W: Write,
+{
+    let mut writer = ::csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(writer);
+    if let Some(headers) = headers {
+        writer.write_record(headers)?;
+    }
+    for point in points {
+        writer.write_record(point.get_vector().iter().map(|v| v.to_string()))?;
+    }
+    writer.flush().map_err(|e| CsvError::Csv(e.into()))?;
+    Ok(())
+}
+
+/// Write points as CSV to a file (see [`write_points`]).
+pub fn write_points_to_path<T, P>(
+    path: P,
+    points: &[Point<T>],
+    headers: Option<&[&str]>,
+) -> Result<(), CsvError>
+where
+    T: Clone + fmt::Display,
+    P: AsRef<Path>,
+{
+    let file = File::create(path).map_err(|e| CsvError::Csv(e.into()))?;
+    write_points(file, points, headers)
+}
@@ -0,0 +1,4 @@
+//! Reading and writing sets of points.
+pub mod binary;
+#[cfg(feature = "csv")]
+pub mod csv;
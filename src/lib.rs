@@ -3,11 +3,49 @@
 
 #![allow(dead_code)]
 use num::traits::Signed;
-use std::ops::{Add, Div, Mul, Sub};
+use num::{Float, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::sync::Arc;
+
+mod approx_eq;
+mod coordinates;
+pub mod delaunay;
+pub mod geo;
+pub mod hyperplane;
+pub mod interpolate;
+pub mod io;
+mod linalg;
+mod ordered;
+mod parse;
+pub mod polyline;
+mod predicates;
+pub mod projective;
+#[cfg(feature = "rand")]
+pub mod random;
+mod schema;
+pub mod sequence;
+pub mod simplex;
+pub mod space_filling;
+pub mod spline;
+pub mod stats;
+mod view;
+pub mod voronoi;
+pub mod voxel;
+pub use approx_eq::Ulps;
+pub use ordered::OrderedPoint;
+pub use parse::ParsePointError;
+pub use schema::Schema;
+pub use view::{PointView, PointViewMut};
+
 /// multidimensional point type.
+#[derive(Debug)]
 pub struct Point<T> {
     values: Vec<T>,
     dim: usize,
+    schema: Option<Arc<Schema>>,
 }
 
 impl<T> Point<T>
@@ -31,6 +69,7 @@ where
         Point {
             values: vec![T::default(); dimension],
             dim: dimension,
+            schema: None,
         }
     }
 }
@@ -49,10 +88,13 @@ where
     /// use multi_dim_point::Point;
     /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
     /// assert_eq!(p1.get_size(), 3);
+    /// ```
+    #[allow(clippy::ptr_arg)]
     pub fn new_from_vec(values_vec: &Vec<T>) -> Point<T> {
         Point {
             values: values_vec.clone(),
             dim: values_vec.len(),
+            schema: None,
         }
     }
 
@@ -155,14 +197,8 @@ where
     ///
     ///```
     pub fn apply_func<S>(&self, other: &Point<T>, f: &dyn Fn(&T, &T) -> S) -> Vec<S> {
-        if self.dim != other.dim {
-            panic!("dimensions are not equal");
-        }
-        self.get_vector()
-            .iter()
-            .zip(other.get_vector().iter())
-            .map(|(a, b)| f(a, b))
-            .collect()
+        self.combined_schema(other);
+        zip_values(&self.values, &other.values, f)
     }
 
     /// The function will return the number of dimensions of the point.
@@ -173,7 +209,7 @@ where
     /// assert_eq!(p1.get_size(), 3);
     /// ```
     pub fn get_size(&self) -> usize {
-        self.dim.clone()
+        self.dim
     }
 }
 
@@ -211,6 +247,7 @@ where
                 .map(|(a, b)| *a + *b)
                 .collect(),
         )
+        .with_schema_of(self.combined_schema(other))
     }
 }
 impl<T> Add for Point<T>
@@ -274,6 +311,7 @@ where
                 .map(|(a, b)| *a - *b)
                 .collect(),
         )
+        .with_schema_of(self.combined_schema(other))
     }
 }
 impl<T> Sub for Point<T>
@@ -316,6 +354,7 @@ where
     /// ```
     fn mul(self, scalar: &S) -> Point<T> {
         Point::new_from_vec(&self.get_vector().iter().map(|a| *a * *scalar).collect())
+            .with_schema_of(self.schema.clone())
     }
 }
 
@@ -352,6 +391,7 @@ where
     /// ```
     fn div(self, scalar: &S) -> Point<T> {
         Point::new_from_vec(&self.get_vector().iter().map(|a| *a / *scalar).collect())
+            .with_schema_of(self.schema.clone())
     }
 }
 
@@ -373,6 +413,194 @@ where
     }
 }
 
+/// Implement the operators that combine a point with a primitive scalar, which can't be
+/// implemented generically (they would overlap with the point-by-point operators).
+macro_rules! impl_scalar_ops {
+    ($($t:ty)*) => ($(
+        impl Mul<&Point<$t>> for &$t {
+            type Output = Point<$t>;
+            /// \* operator, with the scalar on the left. Multiply each value in the point.
+            fn mul(self, point: &Point<$t>) -> Point<$t> {
+                point * self
+            }
+        }
+
+        impl Mul<Point<$t>> for $t {
+            type Output = Point<$t>;
+            /// \* operator, with the scalar on the left. Multiply each value in the point.
+            fn mul(self, point: Point<$t>) -> Point<$t> {
+                &point * &self
+            }
+        }
+
+        impl Add<&$t> for &Point<$t> {
+            type Output = Point<$t>;
+            /// \+ operator. Add the scalar to each value in the point.
+            fn add(self, scalar: &$t) -> Point<$t> {
+                Point::new_from_vec(&self.get_vector().iter().map(|a| *a + *scalar).collect())
+                    .with_schema_of(self.schema.clone())
+            }
+        }
+
+        impl Add<$t> for Point<$t> {
+            type Output = Point<$t>;
+            /// \+ operator. Add the scalar to each value in the point.
+            fn add(self, scalar: $t) -> Point<$t> {
+                &self + &scalar
+            }
+        }
+
+        impl Sub<&$t> for &Point<$t> {
+            type Output = Point<$t>;
+            /// \- operator. Subtract the scalar from each value in the point.
+            fn sub(self, scalar: &$t) -> Point<$t> {
+                Point::new_from_vec(&self.get_vector().iter().map(|a| *a - *scalar).collect())
+                    .with_schema_of(self.schema.clone())
+            }
+        }
+
+        impl Sub<$t> for Point<$t> {
+            type Output = Point<$t>;
+            /// \- operator. Subtract the scalar from each value in the point.
+            fn sub(self, scalar: $t) -> Point<$t> {
+                &self - &scalar
+            }
+        }
+    )*)
+}
+
+impl_scalar_ops! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64 }
+
+impl<T> Point<T>
+where
+    T: Clone + Copy,
+{
+    /// Hadamard product. Multiply the values in each dimension, and return the result as a new
+    /// point.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!(p1.hadamard_mul(&p2).get_vector(), &vec![4,10,18]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    ///
+    /// ```should_panic
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![5,6]);
+    /// p1.hadamard_mul(&p2);
+    /// ```
+    pub fn hadamard_mul(&self, other: &Point<T>) -> Point<T>
+    where
+        T: Mul<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a * *b))
+            .with_schema_of(self.combined_schema(other))
+    }
+
+    /// Hadamard division. Divide the values in each dimension, and return the result as a new
+    /// point.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![4,10,18]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!(p1.hadamard_div(&p2).get_vector(), &vec![1,2,3]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal, or (for integer
+    /// types) when dividing by zero.
+    ///
+    /// ```should_panic
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![5,6]);
+    /// p1.hadamard_div(&p2);
+    /// ```
+    pub fn hadamard_div(&self, other: &Point<T>) -> Point<T>
+    where
+        T: Div<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a / *b))
+            .with_schema_of(self.combined_schema(other))
+    }
+
+    /// Hadamard remainder. Calculate the remainder of the values in each dimension, and return
+    /// the result as a new point.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![7,10,20]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!(p1.hadamard_rem(&p2).get_vector(), &vec![3,0,2]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal, or (for integer
+    /// types) when the divisor is zero.
+    ///
+    /// ```should_panic
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![5,6]);
+    /// p1.hadamard_rem(&p2);
+    /// ```
+    pub fn hadamard_rem(&self, other: &Point<T>) -> Point<T>
+    where
+        T: Rem<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a % *b))
+            .with_schema_of(self.combined_schema(other))
+    }
+}
+
+impl<T> Rem for &Point<T>
+where
+    T: Clone + Copy + Rem<Output = T>,
+{
+    type Output = Point<T>;
+    /// % operator. Remainder of the values in each dimension (see [`Point::hadamard_rem`]).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![7,10,20]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!((&p1 % &p2).get_vector(), &vec![3,0,2]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    ///
+    /// ```should_panic
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![5,6]);
+    /// &p1 % &p2;
+    /// ```
+    fn rem(self, other: Self) -> Point<T> {
+        self.hadamard_rem(other)
+    }
+}
+
+impl<T> Rem for Point<T>
+where
+    T: Clone + Copy + Rem<Output = T>,
+{
+    type Output = Point<T>;
+    /// % operator. Remainder of the values in each dimension (see [`Point::hadamard_rem`]).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![7,10,20]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!((p1 % p2).get_vector(), &vec![3,0,2]);
+    /// ```
+    fn rem(self, other: Self) -> Point<T> {
+        &self % &other
+    }
+}
+
 impl<T> PartialEq for Point<T>
 where
     T: PartialEq + Clone,
@@ -408,6 +636,55 @@ where
     }
 }
 
+impl<T> Eq for Point<T> where T: Eq + Clone {}
+
+impl<T> Hash for Point<T>
+where
+    T: Hash,
+{
+    /// Hash the values of the point, so points can be used as `HashMap` / `HashSet` keys.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// use std::collections::HashMap;
+    /// let mut cells: HashMap<Point<i64>, &str> = HashMap::new();
+    /// cells.insert(Point::new_from_vec(&vec![1,2]), "wall");
+    /// assert_eq!(cells.get(&Point::new_from_vec(&vec![1,2])), Some(&"wall"));
+    /// ```
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<T> PartialOrd for Point<T>
+where
+    T: PartialOrd + Clone,
+{
+    /// Lexicographic order: compare the values dimension after dimension. When one point is a
+    /// prefix of the other, the point with less dimensions is smaller.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,5]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![2,0]);
+    /// assert!(p1 < p2);
+    /// assert!(p1 < Point::new_from_vec(&vec![1,5,0]));
+    /// ```
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.values.partial_cmp(&other.values)
+    }
+}
+
+impl<T> Ord for Point<T>
+where
+    T: Ord + Clone,
+{
+    /// Lexicographic order: compare the values dimension after dimension.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.values.cmp(&other.values)
+    }
+}
+
 impl<T> Point<T>
 where
     T: Sub<Output = T> + PartialOrd + Clone + Copy + Signed,
@@ -442,14 +719,137 @@ where
                 .all(|(a, b)| (*a - *b).abs() <= eps)
     }
 }
+impl<T> Point<T>
+where
+    T: Clone + Copy + Zero + Mul<Output = T>,
+{
+    /// Dot product of the points (as vectors).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// let p2: Point<i32> = Point::new_from_vec(&vec![4,5,6]);
+    /// assert_eq!(p1.dot(&p2), 32);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn dot(&self, other: &Point<T>) -> T {
+        self.combined_schema(other);
+        dot_values(&self.values, &other.values)
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Float,
+{
+    /// Euclidean norm (length) of the point, as a vector.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![3.0, 4.0]);
+    /// assert_eq!(p1.norm(), 5.0);
+    /// ```
+    pub fn norm(&self) -> T {
+        dot_values(&self.values, &self.values).sqrt()
+    }
+
+    /// Euclidean distance between the points.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 1.0]);
+    /// let p2: Point<f64> = Point::new_from_vec(&vec![4.0, 5.0]);
+    /// assert_eq!(p1.distance(&p2), 5.0);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn distance(&self, other: &Point<T>) -> T {
+        self.combined_schema(other);
+        distance_values(&self.values, &other.values)
+    }
+}
+
 impl<T> Clone for Point<T>
 where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        Point::new_from_vec(&self.get_vector())
+        Point::new_from_vec(self.get_vector()).with_schema_of(self.schema.clone())
+    }
+}
+
+impl<T> fmt::Display for Point<T>
+where
+    T: fmt::Display,
+{
+    /// Format the point as `(v1, v2, ..., vn)`. Width and precision are applied to each value.
+    /// # Examples
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1,2,3]);
+    /// assert_eq!(p1.to_string(), "(1, 2, 3)");
+    /// ```
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 2.5]);
+    /// assert_eq!(format!("{:.2}", p1), "(1.00, 2.50)");
+    /// assert_eq!(format!("{:>4}", p1), "(   1,  2.5)");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_values(&self.values, f)
+    }
+}
+
+/// Apply a function on every pair of values in the same dimension. Shared by [`Point`] and the
+/// views.
+/// # Panic
+/// This function will panic if the numbers of values are not equal.
+fn zip_values<T, S>(a: &[T], b: &[T], f: impl Fn(&T, &T) -> S) -> Vec<S> {
+    if a.len() != b.len() {
+        panic!("dimensions are not equal");
+    }
+    a.iter().zip(b).map(|(x, y)| f(x, y)).collect()
+}
+
+/// The dot product of the values.
+/// # Panic
+/// This function will panic if the numbers of values are not equal.
+fn dot_values<T>(a: &[T], b: &[T]) -> T
+where
+    T: Copy + Zero + Mul<Output = T>,
+{
+    if a.len() != b.len() {
+        panic!("dimensions are not equal");
+    }
+    a.iter().zip(b).fold(T::zero(), |sum, (x, y)| sum + *x * *y)
+}
+
+/// The Euclidean distance between the values.
+/// # Panic
+/// This function will panic if the numbers of values are not equal.
+fn distance_values<T: Float>(a: &[T], b: &[T]) -> T {
+    if a.len() != b.len() {
+        panic!("dimensions are not equal");
+    }
+    a.iter()
+        .zip(b)
+        .fold(T::zero(), |sum, (x, y)| sum + (*x - *y) * (*x - *y))
+        .sqrt()
+}
+
+fn fmt_values<T: fmt::Display>(values: &[T], f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "(")?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        value.fmt(f)?;
     }
+    write!(f, ")")
 }
 
+// the original tests compare with bool literals in `assert_eq!`.
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test;
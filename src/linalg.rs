@@ -0,0 +1,96 @@
+//! Small dense linear algebra helpers (Gaussian elimination with partial pivoting).
+use num::Float;
+use std::cmp::Ordering;
+
+/// Eliminate the square matrix (a vector of rows, with extra columns carried along) to upper
+/// triangular form. Returns the determinant of the square part, or `None` (and the elimination
+/// stops) if the matrix is singular.
+fn eliminate<T: Float>(rows: &mut [Vec<T>]) -> Option<T> {
+    let n = rows.len();
+    let scale = rows
+        .iter()
+        .flat_map(|row| row[..n].iter())
+        .fold(T::zero(), |m, v| m.max(v.abs()));
+    let tolerance = scale * T::epsilon() * T::from(n).unwrap();
+    let mut det = T::one();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|a, b| {
+                rows[*a][col]
+                    .abs()
+                    .partial_cmp(&rows[*b][col].abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        let pivot_value = rows[pivot][col].abs();
+        if pivot_value <= tolerance || pivot_value.is_nan() {
+            return None;
+        }
+        if pivot != col {
+            rows.swap(pivot, col);
+            det = -det;
+        }
+        det = det * rows[col][col];
+        for row in col + 1..n {
+            let factor = rows[row][col] / rows[col][col];
+            for k in col..rows[row].len() {
+                let v = rows[col][k];
+                rows[row][k] = rows[row][k] - factor * v;
+            }
+        }
+    }
+    Some(det)
+}
+
+/// Solve the eliminated rows for the extra column `column` (counted after the square part).
+fn back_substitute<T: Float>(rows: &[Vec<T>], column: usize) -> Vec<T> {
+    let n = rows.len();
+    let mut x = vec![T::zero(); n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).fold(rows[row][n + column], |s, k| s - rows[row][k] * x[k]);
+        x[row] = sum / rows[row][row];
+    }
+    x
+}
+
+/// The determinant of the square matrix (a vector of rows).
+pub(crate) fn determinant<T: Float>(matrix: &[Vec<T>]) -> T {
+    let mut rows = matrix.to_vec();
+    eliminate(&mut rows).unwrap_or_else(T::zero)
+}
+
+/// Solve `matrix * x = rhs`, or `None` if the matrix is singular.
+pub(crate) fn solve<T: Float>(matrix: &[Vec<T>], rhs: &[T]) -> Option<Vec<T>> {
+    let mut rows: Vec<Vec<T>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, b)| {
+            let mut row = row.clone();
+            row.push(*b);
+            row
+        })
+        .collect();
+    eliminate(&mut rows)?;
+    Some(back_substitute(&rows, 0))
+}
+
+/// The inverse of the square matrix (a vector of rows), or `None` if it is singular.
+pub(crate) fn inverse<T: Float>(matrix: &[Vec<T>]) -> Option<Vec<Vec<T>>> {
+    let n = matrix.len();
+    let mut rows: Vec<Vec<T>> = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| if i == j { T::one() } else { T::zero() }));
+            row
+        })
+        .collect();
+    eliminate(&mut rows)?;
+    let columns: Vec<Vec<T>> = (0..n).map(|col| back_substitute(&rows, col)).collect();
+    Some(
+        (0..n)
+            .map(|row| columns.iter().map(|c| c[row]).collect())
+            .collect(),
+    )
+}
@@ -0,0 +1,126 @@
+//! Total order for points of floats.
+use crate::Point;
+use num::Float;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// A point of floats with a total order, equality and hash, so it can be used as a key of
+/// `HashMap`, `BTreeMap` etc.
+///
+/// The values are compared like [`Point`]'s lexicographic order, with two changes that make the
+/// order deterministic: `-0.0` is equal to `0.0`, and NaN values are equal to each other and
+/// bigger than every other value (including infinity).
+///
+/// # Example
+/// ```
+/// use multi_dim_point::{OrderedPoint, Point};
+/// use std::collections::BTreeSet;
+/// let mut set = BTreeSet::new();
+/// set.insert(OrderedPoint::new(Point::new_from_vec(&vec![1.0, f64::NAN])));
+/// set.insert(OrderedPoint::new(Point::new_from_vec(&vec![-0.0, 2.0])));
+/// set.insert(OrderedPoint::new(Point::new_from_vec(&vec![0.0, 2.0])));
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.iter().next().unwrap().get_vector(), &vec![-0.0, 2.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderedPoint<T>(Point<T>);
+
+impl<T> OrderedPoint<T>
+where
+    T: Float,
+{
+    /// Wrap a point.
+    pub fn new(point: Point<T>) -> OrderedPoint<T> {
+        OrderedPoint(point)
+    }
+
+    /// Return the wrapped point.
+    pub fn into_inner(self) -> Point<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for OrderedPoint<T> {
+    type Target = Point<T>;
+
+    fn deref(&self) -> &Point<T> {
+        &self.0
+    }
+}
+
+impl<T> From<Point<T>> for OrderedPoint<T>
+where
+    T: Float,
+{
+    fn from(point: Point<T>) -> Self {
+        OrderedPoint::new(point)
+    }
+}
+
+fn total_cmp<T: Float>(a: T, b: T) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        // -0.0 == 0.0 in partial_cmp.
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+impl<T> PartialEq for OrderedPoint<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for OrderedPoint<T> where T: Float {}
+
+impl<T> PartialOrd for OrderedPoint<T>
+where
+    T: Float,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for OrderedPoint<T>
+where
+    T: Float,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.get_vector();
+        let b = other.get_vector();
+        a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| total_cmp(*x, *y))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len()))
+    }
+}
+
+impl<T> Hash for OrderedPoint<T>
+where
+    T: Float,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let values = self.get_vector();
+        values.len().hash(state);
+        for value in values {
+            if value.is_nan() {
+                // same for all the NaN values.
+                state.write_u8(0);
+            } else if value.is_zero() {
+                // same for 0.0 and -0.0.
+                state.write_u8(1);
+            } else {
+                state.write_u8(2);
+                value.integer_decode().hash(state);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Text parsing of points.
+use crate::Point;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when parsing a point from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsePointError<E> {
+    /// The string is empty (or contains only whitespace).
+    Empty,
+    /// The string opens with a bracket that is not closed with the matching bracket (or the
+    /// other way around).
+    UnbalancedBrackets,
+    /// One of the values could not be parsed. The dimension starts from 1, like in
+    /// [`Point::get_value`].
+    InvalidValue {
+        dimension: usize,
+        value: String,
+        error: E,
+    },
+}
+
+impl<E> fmt::Display for ParsePointError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePointError::Empty => write!(f, "cannot parse a point from an empty string"),
+            ParsePointError::UnbalancedBrackets => write!(f, "unbalanced brackets"),
+            ParsePointError::InvalidValue {
+                dimension,
+                value,
+                error,
+            } => write!(
+                f,
+                "invalid value {:?} in dimension {}: {}",
+                value, dimension, error
+            ),
+        }
+    }
+}
+
+impl<E> Error for ParsePointError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParsePointError::InvalidValue { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Remove the surrounding brackets (if any) from the string.
+fn strip_brackets(s: &str) -> Option<&str> {
+    for (open, close) in &[('(', ')'), ('[', ']'), ('{', '}')] {
+        let starts = s.starts_with(*open);
+        let ends = s.ends_with(*close);
+        if starts != ends || (starts && s.len() < 2) {
+            return None;
+        }
+        if starts {
+            return Some(&s[1..s.len() - 1]);
+        }
+    }
+    Some(s)
+}
+
+impl<T> FromStr for Point<T>
+where
+    T: Clone + FromStr,
+{
+    type Err = ParsePointError<T::Err>;
+
+    /// Parse a point from a string. The values can be separated by commas, semicolons or
+    /// whitespace, and optionally surrounded with `()`, `[]` or `{}`.
+    /// # Examples
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = "(1, 2, 3)".parse().unwrap();
+    /// let p2: Point<i32> = "[1 2 3]".parse().unwrap();
+    /// let p3: Point<i32> = "1;2;3".parse().unwrap();
+    /// assert_eq!(p1.get_vector(), &vec![1,2,3]);
+    /// assert_eq!(p2.get_vector(), &vec![1,2,3]);
+    /// assert_eq!(p3.get_vector(), &vec![1,2,3]);
+    /// ```
+    /// ```
+    /// use multi_dim_point::{ParsePointError, Point};
+    /// let err = "(1, x, 3)".parse::<Point<i32>>().unwrap_err();
+    /// assert!(matches!(err, ParsePointError::InvalidValue { dimension: 2, .. }));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParsePointError::Empty);
+        }
+        let inner = strip_brackets(s).ok_or(ParsePointError::UnbalancedBrackets)?;
+        let inner = inner.trim();
+        if inner.is_empty() {
+            return Ok(Point::new_from_vec(&Vec::new()));
+        }
+        let tokens: Vec<&str> = if inner.contains([',', ';']) {
+            inner.split([',', ';']).map(str::trim).collect()
+        } else {
+            inner.split_whitespace().collect()
+        };
+        let mut values = Vec::with_capacity(tokens.len());
+        for (i, token) in tokens.iter().enumerate() {
+            match token.parse() {
+                Ok(value) => values.push(value),
+                Err(error) => {
+                    return Err(ParsePointError::InvalidValue {
+                        dimension: i + 1,
+                        value: token.to_string(),
+                        error,
+                    })
+                }
+            }
+        }
+        Ok(Point::new_from_vec(&values))
+    }
+}
@@ -0,0 +1,414 @@
+//! Polylines (sequences of points connected by straight segments) of any dimension.
+use crate::Point;
+use num::Float;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// A sequence of points connected by straight segments.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::polyline::Polyline;
+/// use multi_dim_point::Point;
+/// let line = Polyline::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![3.0, 4.0]),
+///     Point::new_from_vec(&vec![3.0, 10.0]),
+/// ]);
+/// assert_eq!(line.length(), 11.0);
+/// assert_eq!(line.cumulative_lengths(), vec![0.0, 5.0, 11.0]);
+/// assert_eq!(line.point_at_distance(8.0).get_vector(), &vec![3.0, 7.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Polyline<T> {
+    points: Vec<Point<T>>,
+}
+
+/// The nearest point of a polyline to some point (see [`Polyline::nearest_point`]).
+#[derive(Debug, Clone)]
+pub struct NearestPoint<T> {
+    /// The nearest point on the polyline.
+    pub point: Point<T>,
+    /// The index of the segment of the nearest point (segment `i` is between the points `i` and
+    /// `i + 1`).
+    pub segment: usize,
+    /// The distance of the nearest point from the start of the polyline, along the polyline.
+    pub along: T,
+    /// The distance from the point to the polyline.
+    pub distance: T,
+}
+
+/// `(t, squared distance)` of the point on segment `a`-`b` nearest to `p`, where `t` is the
+/// relative position on the segment (0 in `a`, 1 in `b`).
+fn project<T: Float>(p: &Point<T>, a: &Point<T>, b: &Point<T>) -> (T, T) {
+    let ab = a.apply_func(b, &|x, y| *y - *x);
+    let ap = a.apply_func(p, &|x, y| *y - *x);
+    let dot = |u: &[T], v: &[T]| {
+        u.iter()
+            .zip(v.iter())
+            .fold(T::zero(), |sum, (x, y)| sum + *x * *y)
+    };
+    let len2 = dot(&ab, &ab);
+    let t = if len2.is_zero() {
+        T::zero()
+    } else {
+        (dot(&ap, &ab) / len2).max(T::zero()).min(T::one())
+    };
+    let d2 = ap
+        .iter()
+        .zip(ab.iter())
+        .fold(T::zero(), |sum, (x, y)| sum + (*x - *y * t) * (*x - *y * t));
+    (t, d2)
+}
+
+/// The area of the triangle `a`, `b`, `c` (in any dimension).
+fn triangle_area<T: Float>(a: &Point<T>, b: &Point<T>, c: &Point<T>) -> T {
+    let u = b.apply_func(a, &|x, y| *y - *x);
+    let v = b.apply_func(c, &|x, y| *y - *x);
+    let dot = |u: &[T], v: &[T]| {
+        u.iter()
+            .zip(v.iter())
+            .fold(T::zero(), |sum, (x, y)| sum + *x * *y)
+    };
+    let (uu, vv, uv) = (dot(&u, &u), dot(&v, &v), dot(&u, &v));
+    // |u|^2 |v|^2 - (u.v)^2 is the squared area of the parallelogram (Lagrange's identity).
+    (uu * vv - uv * uv).max(T::zero()).sqrt() / T::from(2).unwrap()
+}
+
+impl<T> Polyline<T>
+where
+    T: Float,
+{
+    /// Creates a polyline through the points.
+    /// # Panic
+    /// This function will panic when there are no points, or when their dimensions are not
+    /// equal.
+    pub fn new(points: Vec<Point<T>>) -> Polyline<T> {
+        if points.is_empty() {
+            panic!("a polyline needs at least one point");
+        }
+        let dim = points[0].get_size();
+        if points.iter().any(|p| p.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        Polyline { points }
+    }
+
+    /// Return the points of the polyline.
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Return the points of the polyline.
+    pub fn into_points(self) -> Vec<Point<T>> {
+        self.points
+    }
+
+    /// Return the number of points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Always `false`, a polyline has at least one point.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The total length of the polyline.
+    pub fn length(&self) -> T {
+        self.points
+            .windows(2)
+            .fold(T::zero(), |sum, pair| sum + pair[0].distance(&pair[1]))
+    }
+
+    /// The distance of each point from the start of the polyline, along the polyline.
+    pub fn cumulative_lengths(&self) -> Vec<T> {
+        let mut lengths = Vec::with_capacity(self.points.len());
+        lengths.push(T::zero());
+        for pair in self.points.windows(2) {
+            let last = *lengths.last().unwrap();
+            lengths.push(last + pair[0].distance(&pair[1]));
+        }
+        lengths
+    }
+
+    /// The point in distance `s` from the start of the polyline, along the polyline (clamped to
+    /// the polyline). The values of the point are NaN if `s` or the length of the polyline is.
+    pub fn point_at_distance(&self, s: T) -> Point<T> {
+        self.point_at(&self.cumulative_lengths(), s)
+    }
+
+    fn point_at(&self, lengths: &[T], s: T) -> Point<T> {
+        let last = self.points.len() - 1;
+        if s <= T::zero() || last == 0 {
+            return self.points[0].clone();
+        }
+        if s.is_nan() || lengths[last].is_nan() {
+            return Point::new_from_vec(&vec![T::nan(); self.points[0].get_size()]);
+        }
+        if s >= lengths[last] {
+            return self.points[last].clone();
+        }
+        // the segment that ends after s.
+        let i = lengths.partition_point(|l| *l <= s).clamp(1, last) - 1;
+        let segment = lengths[i + 1] - lengths[i];
+        self.points[i].lerp(&self.points[i + 1], (s - lengths[i]) / segment)
+    }
+
+    /// Points along the polyline, `step` apart (by distance along the polyline), from the start
+    /// of the polyline. The end of the polyline is always the last point, so the last step may
+    /// be shorter.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::polyline::Polyline;
+    /// use multi_dim_point::Point;
+    /// let line = Polyline::new(vec![
+    ///     Point::new_from_vec(&vec![0.0]),
+    ///     Point::new_from_vec(&vec![5.0]),
+    /// ]);
+    /// let resampled = line.resample(2.0);
+    /// let xs: Vec<f64> = resampled.points().iter().map(|p| *p.get_value(1)).collect();
+    /// assert_eq!(xs, vec![0.0, 2.0, 4.0, 5.0]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the step is not positive.
+    pub fn resample(&self, step: T) -> Polyline<T> {
+        if step <= T::zero() || step.is_nan() {
+            panic!("the step must be positive");
+        }
+        let lengths = self.cumulative_lengths();
+        let total = *lengths.last().unwrap();
+        let mut points = Vec::new();
+        let mut s = T::zero();
+        while s < total - step * T::from(1e-9).unwrap() {
+            points.push(self.point_at(&lengths, s));
+            s = s + step;
+        }
+        points.push(self.points.last().unwrap().clone());
+        Polyline::new(points)
+    }
+
+    /// `count` points along the polyline, equally spaced by distance, including both ends.
+    /// # Panic
+    /// This function will panic if `count` is smaller than 2.
+    pub fn resample_count(&self, count: usize) -> Polyline<T> {
+        if count < 2 {
+            panic!("resampling needs at least 2 points ({} < 2)", count);
+        }
+        let lengths = self.cumulative_lengths();
+        let total = *lengths.last().unwrap();
+        let n = T::from(count - 1).unwrap();
+        Polyline::new(
+            (0..count)
+                .map(|i| self.point_at(&lengths, total * T::from(i).unwrap() / n))
+                .collect(),
+        )
+    }
+
+    /// The point of the polyline nearest to `p`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::polyline::Polyline;
+    /// use multi_dim_point::Point;
+    /// let line = Polyline::new(vec![
+    ///     Point::new_from_vec(&vec![0.0, 0.0]),
+    ///     Point::new_from_vec(&vec![10.0, 0.0]),
+    ///     Point::new_from_vec(&vec![10.0, 10.0]),
+    /// ]);
+    /// let nearest = line.nearest_point(&Point::new_from_vec(&vec![12.0, 4.0]));
+    /// assert_eq!(nearest.point.get_vector(), &vec![10.0, 4.0]);
+    /// assert_eq!(nearest.segment, 1);
+    /// assert_eq!(nearest.along, 14.0);
+    /// assert_eq!(nearest.distance, 2.0);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimension of the point is not the polyline's dimension.
+    pub fn nearest_point(&self, p: &Point<T>) -> NearestPoint<T> {
+        if p.get_size() != self.points[0].get_size() {
+            panic!("dimensions are not equal");
+        }
+        if self.points.len() == 1 {
+            return NearestPoint {
+                point: self.points[0].clone(),
+                segment: 0,
+                along: T::zero(),
+                distance: p.distance(&self.points[0]),
+            };
+        }
+        let lengths = self.cumulative_lengths();
+        let (segment, t, d2) = self
+            .points
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let (t, d2) = project(p, &pair[0], &pair[1]);
+                (i, t, d2)
+            })
+            .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+            .unwrap();
+        NearestPoint {
+            point: self.points[segment].lerp(&self.points[segment + 1], t),
+            segment,
+            along: lengths[segment] + (lengths[segment + 1] - lengths[segment]) * t,
+            distance: d2.sqrt(),
+        }
+    }
+
+    /// Simplify the polyline by the Ramer–Douglas–Peucker algorithm: keep the end points, and
+    /// recursively keep the point farthest from the simplified segment while its distance is
+    /// bigger than `epsilon`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::polyline::Polyline;
+    /// use multi_dim_point::Point;
+    /// let line = Polyline::new(vec![
+    ///     Point::new_from_vec(&vec![0.0, 0.0]),
+    ///     Point::new_from_vec(&vec![1.0, 0.1]),
+    ///     Point::new_from_vec(&vec![2.0, 0.0]),
+    ///     Point::new_from_vec(&vec![3.0, 5.0]),
+    /// ]);
+    /// assert_eq!(line.simplify_rdp(0.5).len(), 3);
+    /// ```
+    pub fn simplify_rdp(&self, epsilon: T) -> Polyline<T> {
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[n - 1] = true;
+        let eps2 = epsilon * epsilon;
+        let mut stack = vec![(0, n - 1)];
+        while let Some((first, last)) = stack.pop() {
+            let farthest = (first + 1..last)
+                .map(|i| {
+                    let (_, d2) = project(&self.points[i], &self.points[first], &self.points[last]);
+                    (i, d2)
+                })
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+            if let Some((i, d2)) = farthest {
+                if d2 > eps2 {
+                    keep[i] = true;
+                    stack.push((first, i));
+                    stack.push((i, last));
+                }
+            }
+        }
+        self.kept(&keep)
+    }
+
+    /// Simplify the polyline by Visvalingam–Whyatt algorithm: repeatedly remove the point whose
+    /// triangle with its neighbours has the smallest area, while that area is smaller than
+    /// `min_area`. The end points are always kept.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::polyline::Polyline;
+    /// use multi_dim_point::Point;
+    /// let line = Polyline::new(vec![
+    ///     Point::new_from_vec(&vec![0.0, 0.0]),
+    ///     Point::new_from_vec(&vec![1.0, 0.1]),
+    ///     Point::new_from_vec(&vec![2.0, 0.0]),
+    ///     Point::new_from_vec(&vec![3.0, 5.0]),
+    /// ]);
+    /// assert_eq!(line.simplify_visvalingam(0.5).len(), 3);
+    /// ```
+    pub fn simplify_visvalingam(&self, min_area: T) -> Polyline<T> {
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+        let mut next: Vec<usize> = (0..n).map(|i| (i + 1).min(n - 1)).collect();
+        let mut keep = vec![true; n];
+        // the current area of every point, entries in the heap with another area are stale.
+        let mut areas = vec![T::infinity(); n];
+        let mut heap = BinaryHeap::new();
+        for (i, triple) in self.points.windows(3).enumerate() {
+            areas[i + 1] = triangle_area(&triple[0], &triple[1], &triple[2]);
+            heap.push(Candidate {
+                area: areas[i + 1],
+                index: i + 1,
+            });
+        }
+        while let Some(Candidate { area, index }) = heap.pop() {
+            if !keep[index] || area != areas[index] {
+                continue;
+            }
+            if area >= min_area || area.is_nan() {
+                break;
+            }
+            keep[index] = false;
+            let (p, q) = (prev[index], next[index]);
+            next[p] = q;
+            prev[q] = p;
+            for &i in &[p, q] {
+                if i == 0 || i == n - 1 {
+                    continue;
+                }
+                let new_area = triangle_area(
+                    &self.points[prev[i]],
+                    &self.points[i],
+                    &self.points[next[i]],
+                );
+                // a point can't be removed before the point that was removed next to it.
+                areas[i] = new_area.max(area);
+                heap.push(Candidate {
+                    area: areas[i],
+                    index: i,
+                });
+            }
+        }
+        self.kept(&keep)
+    }
+
+    fn kept(&self, keep: &[bool]) -> Polyline<T> {
+        Polyline::new(
+            self.points
+                .iter()
+                .zip(keep.iter())
+                .filter(|(_, k)| **k)
+                .map(|(p, _)| p.clone())
+                .collect(),
+        )
+    }
+}
+
+/// A point in Visvalingam's heap, ordered by smallest area first.
+struct Candidate<T> {
+    area: T,
+    index: usize,
+}
+
+impl<T: Float> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for Candidate<T> {}
+
+impl<T: Float> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Float> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, BinaryHeap is a max-heap.
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<T> From<Vec<Point<T>>> for Polyline<T>
+where
+    T: Float,
+{
+    fn from(points: Vec<Point<T>>) -> Self {
+        Polyline::new(points)
+    }
+}
@@ -0,0 +1,246 @@
+//! Exact geometric predicates (orientation and insphere) for `f64` coordinates.
+//!
+//! A predicate is first evaluated in floating point; when the result is too close to 0 to be
+//! certain of its sign, it is evaluated again exactly with the expansion arithmetic of
+//! J. R. Shewchuk ("Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric
+//! Predicates", 1997): a value is a sum of non-overlapping `f64` components, in increasing
+//! magnitude. Points whose products would overflow are scaled down by a power of 2 first.
+use std::cmp::Ordering;
+
+type Expansion = Vec<f64>;
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn push_non_zero(e: &mut Expansion, value: f64) {
+    if value != 0.0 {
+        e.push(value);
+    }
+}
+
+/// The exact difference `a - b`.
+fn difference(a: f64, b: f64) -> Expansion {
+    let (x, y) = two_sum(a, -b);
+    let mut e = Vec::with_capacity(2);
+    push_non_zero(&mut e, y);
+    push_non_zero(&mut e, x);
+    e
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Expansion {
+    let mut merged: Vec<f64> = Vec::with_capacity(e.len() + f.len());
+    let (mut i, mut j) = (0, 0);
+    while i < e.len() || j < f.len() {
+        if j == f.len() || (i < e.len() && e[i].abs() < f[j].abs()) {
+            merged.push(e[i]);
+            i += 1;
+        } else {
+            merged.push(f[j]);
+            j += 1;
+        }
+    }
+    let mut result = Vec::with_capacity(merged.len());
+    let mut components = merged.into_iter();
+    let mut q = match components.next() {
+        Some(q) => q,
+        None => return result,
+    };
+    for g in components {
+        let (x, y) = two_sum(q, g);
+        push_non_zero(&mut result, y);
+        q = x;
+    }
+    push_non_zero(&mut result, q);
+    result
+}
+
+fn scale(e: &[f64], b: f64) -> Expansion {
+    let mut result = Vec::with_capacity(2 * e.len());
+    let mut components = e.iter();
+    let (mut q, low) = match components.next() {
+        Some(first) => two_product(*first, b),
+        None => return result,
+    };
+    push_non_zero(&mut result, low);
+    for c in components {
+        let (high, low) = two_product(*c, b);
+        let (x, y) = two_sum(q, low);
+        push_non_zero(&mut result, y);
+        let (x, y) = fast_two_sum(high, x);
+        push_non_zero(&mut result, y);
+        q = x;
+    }
+    push_non_zero(&mut result, q);
+    result
+}
+
+fn product(e: &[f64], f: &[f64]) -> Expansion {
+    f.iter()
+        .fold(Vec::new(), |total, c| sum(&total, &scale(e, *c)))
+}
+
+fn negate(e: &[f64]) -> Expansion {
+    e.iter().map(|c| -c).collect()
+}
+
+fn sign(e: &[f64]) -> Ordering {
+    // the last component has the largest magnitude, so it has the sign of the sum.
+    e.last().map_or(Ordering::Equal, |c| {
+        c.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+    })
+}
+
+/// The exact determinant of a square matrix, by cofactor expansion along the first row.
+fn determinant(matrix: &[Vec<Expansion>]) -> Expansion {
+    if matrix.len() == 1 {
+        return matrix[0][0].clone();
+    }
+    let mut total = Vec::new();
+    for col in 0..matrix.len() {
+        let minor: Vec<Vec<Expansion>> = matrix[1..]
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(c, _)| *c != col)
+                    .map(|(_, v)| v.clone())
+                    .collect()
+            })
+            .collect();
+        let term = product(&matrix[0][col], &determinant(&minor));
+        total = if col % 2 == 0 {
+            sum(&total, &term)
+        } else {
+            sum(&total, &negate(&term))
+        };
+    }
+    total
+}
+
+/// The determinant and the permanent (the determinant without the signs, of the absolute
+/// values, which bounds the rounding error) of a matrix up to 4x4, in floating point.
+fn approximate_determinant(m: &[[f64; 4]; 4], n: usize) -> (f64, f64) {
+    let det2 = |a: usize, b: usize, c: usize, d: usize| {
+        (
+            m[a][c] * m[b][d] - m[a][d] * m[b][c],
+            (m[a][c] * m[b][d]).abs() + (m[a][d] * m[b][c]).abs(),
+        )
+    };
+    // the 3x3 minor of rows `r` and columns `c`
+    let det3 = |r: [usize; 3], c: [usize; 3]| {
+        let (d0, p0) = det2(r[1], r[2], c[1], c[2]);
+        let (d1, p1) = det2(r[1], r[2], c[0], c[2]);
+        let (d2, p2) = det2(r[1], r[2], c[0], c[1]);
+        (
+            m[r[0]][c[0]] * d0 - m[r[0]][c[1]] * d1 + m[r[0]][c[2]] * d2,
+            m[r[0]][c[0]].abs() * p0 + m[r[0]][c[1]].abs() * p1 + m[r[0]][c[2]].abs() * p2,
+        )
+    };
+    match n {
+        1 => (m[0][0], m[0][0].abs()),
+        2 => det2(0, 1, 0, 1),
+        3 => det3([0, 1, 2], [0, 1, 2]),
+        _ => {
+            let minors = [[1, 2, 3], [0, 2, 3], [0, 1, 3], [0, 1, 2]];
+            (0..4).fold((0.0, 0.0), |(det, permanent), col| {
+                let (d, p) = det3([1, 2, 3], minors[col]);
+                let sign = if col % 2 == 0 { 1.0 } else { -1.0 };
+                (det + sign * m[0][col] * d, permanent + m[0][col].abs() * p)
+            })
+        }
+    }
+}
+
+/// The pairs of points scaled by a power of 2 (exactly, but for values that become subnormal),
+/// so the largest value is at most 1. The signs of the determinants don't change.
+fn scaled_pairs(pairs: &[(&[f64], &[f64])]) -> Vec<(Vec<f64>, Vec<f64>)> {
+    let largest = pairs
+        .iter()
+        .flat_map(|(a, b)| a.iter().chain(b.iter()))
+        .fold(0.0_f64, |m, v| m.max(v.abs()));
+    let factor = 2.0_f64.powi(-(largest.log2().ceil() as i32));
+    let scale = |p: &[f64]| p.iter().map(|v| v * factor).collect();
+    pairs.iter().map(|(a, b)| (scale(a), scale(b))).collect()
+}
+
+/// The sign of the determinant of the matrix of `rows(a, b) = a - b` and (optionally) the
+/// lifted value `|a - b|^2`, for the given pairs of points (a matrix up to 4x4). `Equal` if a
+/// value is not finite.
+fn sign_of_rows(pairs: &[(&[f64], &[f64])], lifted: bool) -> Ordering {
+    if pairs
+        .iter()
+        .any(|(a, b)| a.iter().chain(b.iter()).any(|v| !v.is_finite()))
+    {
+        return Ordering::Equal;
+    }
+    let n = pairs.len();
+    let mut approx = [[0.0; 4]; 4];
+    for (row, (a, b)) in approx.iter_mut().zip(pairs) {
+        for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+            row[i] = x - y;
+        }
+        if lifted {
+            row[n - 1] = row[..n - 1].iter().map(|v| v * v).sum();
+        }
+    }
+    let (det, permanent) = approximate_determinant(&approx, n);
+    if !permanent.is_finite() {
+        // the products overflow: evaluate again with the points scaled down, where they don't.
+        let scaled = scaled_pairs(pairs);
+        let pairs: Vec<(&[f64], &[f64])> = scaled
+            .iter()
+            .map(|(a, b)| (a.as_slice(), b.as_slice()))
+            .collect();
+        return sign_of_rows(&pairs, lifted);
+    }
+    // the rounding errors are far below this bound for matrices up to 4x4.
+    if det.abs() > 1e-12 * permanent {
+        return det.partial_cmp(&0.0).unwrap_or(Ordering::Equal);
+    }
+    let exact: Vec<Vec<Expansion>> = pairs
+        .iter()
+        .map(|(a, b)| {
+            let mut row: Vec<Expansion> = a
+                .iter()
+                .zip(b.iter())
+                .map(|(x, y)| difference(*x, *y))
+                .collect();
+            if lifted {
+                let squares = row.iter().fold(Vec::new(), |s, d| sum(&s, &product(d, d)));
+                row.push(squares);
+            }
+            row
+        })
+        .collect();
+    sign(&determinant(&exact))
+}
+
+/// The orientation of `d + 1` points in `d` dimensions: the sign of the determinant of the rows
+/// `points[i] - points[d]`. In 2D, `Greater` means counterclockwise.
+pub(crate) fn orient(points: &[&[f64]]) -> Ordering {
+    let (last, rest) = points.split_last().unwrap();
+    let pairs: Vec<(&[f64], &[f64])> = rest.iter().map(|p| (*p, *last)).collect();
+    sign_of_rows(&pairs, false)
+}
+
+/// `Greater` if the point is inside the circumsphere of the `d + 1` points in `d` dimensions,
+/// `Less` if it is outside, and `Equal` if it is on the sphere. The points must have a positive
+/// orientation.
+pub(crate) fn insphere(points: &[&[f64]], point: &[f64]) -> Ordering {
+    let pairs: Vec<(&[f64], &[f64])> = points.iter().map(|p| (*p, point)).collect();
+    sign_of_rows(&pairs, true)
+}
@@ -0,0 +1,343 @@
+//! Homogeneous coordinates, projective transforms and a pinhole camera model.
+use crate::linalg;
+use crate::Point;
+use num::Float;
+
+impl<T> Point<T>
+where
+    T: Float,
+{
+    /// Convert the point to homogeneous coordinates, by appending `w = 1`.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 2.0]);
+    /// assert_eq!(p1.to_homogeneous().get_vector(), &vec![1.0, 2.0, 1.0]);
+    /// ```
+    pub fn to_homogeneous(&self) -> Point<T> {
+        let mut values = self.get_vector().clone();
+        values.push(T::one());
+        Point::new_from_vec(&values)
+    }
+
+    /// Convert the point from homogeneous coordinates, by dividing by the last value (`w`) and
+    /// removing it. Returns `None` for points at infinity (`w = 0`).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<f64> = Point::new_from_vec(&vec![2.0, 4.0, 2.0]);
+    /// assert_eq!(p1.from_homogeneous().unwrap().get_vector(), &vec![1.0, 2.0]);
+    /// assert!(Point::new_from_vec(&vec![1.0, 0.0]).from_homogeneous().is_none());
+    /// ```
+    /// # Panic
+    /// This function will panic if the point has no dimensions.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_homogeneous(&self) -> Option<Point<T>> {
+        let (w, values) = match self.get_vector().split_last() {
+            Some(split) => split,
+            None => panic!("a point in homogeneous coordinates has at least one dimension"),
+        };
+        if w.is_zero() {
+            return None;
+        }
+        Some(Point::new_from_vec(
+            &values.iter().map(|v| *v / *w).collect(),
+        ))
+    }
+}
+
+/// A projective transform of N-dimensional points, represented by an `(N+1)x(N+1)` matrix that
+/// multiplies points in homogeneous coordinates (as column vectors).
+///
+/// # Example
+/// ```
+/// use multi_dim_point::projective::ProjectiveTransform;
+/// use multi_dim_point::Point;
+/// let move_then_scale = ProjectiveTransform::translation(&Point::new_from_vec(&vec![1.0, 2.0]))
+///     .then(&ProjectiveTransform::scaling(&Point::new_from_vec(&vec![2.0, 3.0])));
+/// let p = move_then_scale.apply(&Point::new_from_vec(&vec![1.0, 1.0])).unwrap();
+/// assert_eq!(p.get_vector(), &vec![4.0, 9.0]);
+/// let back = move_then_scale.inverse().unwrap().apply(&p).unwrap();
+/// assert!(back.close(&Point::new_from_vec(&vec![1.0, 1.0]), 1e-12));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectiveTransform<T> {
+    dim: usize,
+    /// `(dim + 1) * (dim + 1)` values, row after row.
+    matrix: Vec<T>,
+}
+
+impl<T> ProjectiveTransform<T>
+where
+    T: Float,
+{
+    /// Creates a transform of `dim`-dimensional points from its matrix (row after row).
+    /// # Panic
+    /// This function will panic if the matrix doesn't have `(dim + 1) * (dim + 1)` values.
+    pub fn from_matrix(dim: usize, matrix: Vec<T>) -> ProjectiveTransform<T> {
+        if matrix.len() != (dim + 1) * (dim + 1) {
+            panic!(
+                "a transform of {} dimensions needs {} values, found {}",
+                dim,
+                (dim + 1) * (dim + 1),
+                matrix.len()
+            );
+        }
+        ProjectiveTransform { dim, matrix }
+    }
+
+    /// The identity transform of `dim`-dimensional points.
+    pub fn identity(dim: usize) -> ProjectiveTransform<T> {
+        let n = dim + 1;
+        let matrix = (0..n * n)
+            .map(|i| if i / n == i % n { T::one() } else { T::zero() })
+            .collect();
+        ProjectiveTransform { dim, matrix }
+    }
+
+    /// Translation by the vector.
+    pub fn translation(offset: &Point<T>) -> ProjectiveTransform<T> {
+        let mut transform = ProjectiveTransform::identity(offset.get_size());
+        for (i, v) in offset.get_vector().iter().enumerate() {
+            transform.set(i, transform.dim, *v);
+        }
+        transform
+    }
+
+    /// Scaling of each dimension by the factor in the point.
+    pub fn scaling(factors: &Point<T>) -> ProjectiveTransform<T> {
+        let mut transform = ProjectiveTransform::identity(factors.get_size());
+        for (i, v) in factors.get_vector().iter().enumerate() {
+            transform.set(i, i, *v);
+        }
+        transform
+    }
+
+    /// The dimension of the transformed points.
+    pub fn get_size(&self) -> usize {
+        self.dim
+    }
+
+    /// The matrix of the transform, row after row.
+    pub fn matrix(&self) -> &[T] {
+        &self.matrix
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        self.matrix[row * (self.dim + 1) + col]
+    }
+
+    fn set(&mut self, row: usize, col: usize, value: T) {
+        self.matrix[row * (self.dim + 1) + col] = value;
+    }
+
+    /// The transform that applies this transform, and then the other transform.
+    /// # Panic
+    /// This function will panic if the dimensions of the transforms are not equal.
+    pub fn then(&self, other: &ProjectiveTransform<T>) -> ProjectiveTransform<T> {
+        if self.dim != other.dim {
+            panic!("dimensions are not equal");
+        }
+        let n = self.dim + 1;
+        let matrix = (0..n * n)
+            .map(|i| {
+                let (row, col) = (i / n, i % n);
+                (0..n).fold(T::zero(), |sum, k| {
+                    sum + other.get(row, k) * self.get(k, col)
+                })
+            })
+            .collect();
+        ProjectiveTransform {
+            dim: self.dim,
+            matrix,
+        }
+    }
+
+    /// The inverse transform, or `None` if the matrix is singular (relative to the size of its
+    /// values) or has NaN values.
+    pub fn inverse(&self) -> Option<ProjectiveTransform<T>> {
+        let n = self.dim + 1;
+        let rows: Vec<Vec<T>> = self.matrix.chunks(n).map(|row| row.to_vec()).collect();
+        let inverse = linalg::inverse(&rows)?;
+        Some(ProjectiveTransform {
+            dim: self.dim,
+            matrix: inverse.concat(),
+        })
+    }
+
+    /// Apply the transform on a point in homogeneous coordinates (with `N+1` dimensions).
+    /// # Panic
+    /// This function will panic if the point doesn't have `N+1` dimensions.
+    pub fn apply_homogeneous(&self, point: &Point<T>) -> Point<T> {
+        let n = self.dim + 1;
+        if point.get_size() != n {
+            panic!(
+                "expected a point with {} dimensions, found {}",
+                n,
+                point.get_size()
+            );
+        }
+        let values = point.get_vector();
+        Point::new_from_vec(
+            &(0..n)
+                .map(|row| (0..n).fold(T::zero(), |sum, k| sum + self.get(row, k) * values[k]))
+                .collect(),
+        )
+    }
+
+    /// Apply the transform on a point. Returns `None` if the point is mapped to infinity.
+    /// # Panic
+    /// This function will panic if the point doesn't have `N` dimensions.
+    pub fn apply(&self, point: &Point<T>) -> Option<Point<T>> {
+        if point.get_size() != self.dim {
+            panic!(
+                "expected a point with {} dimensions, found {}",
+                self.dim,
+                point.get_size()
+            );
+        }
+        self.apply_homogeneous(&point.to_homogeneous())
+            .from_homogeneous()
+    }
+}
+
+/// A pinhole camera, projecting 3D world points to 2D image points (in pixels).
+///
+/// The camera looks along its z axis, with the image x axis to the right and the image y axis
+/// down. The pose maps world points to camera coordinates: `camera = R * world + t`.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::projective::PinholeCamera;
+/// use multi_dim_point::Point;
+/// let camera = PinholeCamera::new(500.0, 500.0, 320.0, 240.0);
+/// let world = Point::new_from_vec(&vec![0.2, -0.1, 2.0]);
+/// let pixel = camera.project(&world).unwrap();
+/// assert_eq!(pixel.get_vector(), &vec![370.0, 215.0]);
+/// assert!(camera.unproject(&pixel, 2.0).close(&world, 1e-12));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinholeCamera<T> {
+    fx: T,
+    fy: T,
+    cx: T,
+    cy: T,
+    rotation: [[T; 3]; 3],
+    translation: [T; 3],
+}
+
+impl<T> PinholeCamera<T>
+where
+    T: Float,
+{
+    /// Creates a camera in the origin of the world, with the focal lengths `fx`, `fy` and the
+    /// principal point `(cx, cy)`, in pixels.
+    pub fn new(fx: T, fy: T, cx: T, cy: T) -> PinholeCamera<T> {
+        let (o, z) = (T::one(), T::zero());
+        PinholeCamera {
+            fx,
+            fy,
+            cx,
+            cy,
+            rotation: [[o, z, z], [z, o, z], [z, z, o]],
+            translation: [z, z, z],
+        }
+    }
+
+    /// Set the pose of the camera: the rotation (row after row) and translation from world
+    /// coordinates to camera coordinates.
+    /// # Panic
+    /// This function will panic if the translation is not 3-dimensional.
+    pub fn with_pose(mut self, rotation: [[T; 3]; 3], translation: &Point<T>) -> PinholeCamera<T> {
+        if translation.get_size() != 3 {
+            panic!(
+                "expected a point with 3 dimensions, found {}",
+                translation.get_size()
+            );
+        }
+        self.rotation = rotation;
+        for (i, v) in translation.get_vector().iter().enumerate() {
+            self.translation[i] = *v;
+        }
+        self
+    }
+
+    /// Convert a world point to camera coordinates.
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn to_camera(&self, world: &Point<T>) -> Point<T> {
+        if world.get_size() != 3 {
+            panic!(
+                "expected a point with 3 dimensions, found {}",
+                world.get_size()
+            );
+        }
+        let p = world.get_vector();
+        Point::new_from_vec(
+            &(0..3)
+                .map(|r| {
+                    self.rotation[r][0] * p[0]
+                        + self.rotation[r][1] * p[1]
+                        + self.rotation[r][2] * p[2]
+                        + self.translation[r]
+                })
+                .collect(),
+        )
+    }
+
+    /// Convert a point in camera coordinates to a world point.
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn to_world(&self, camera: &Point<T>) -> Point<T> {
+        if camera.get_size() != 3 {
+            panic!(
+                "expected a point with 3 dimensions, found {}",
+                camera.get_size()
+            );
+        }
+        let p = camera.get_vector();
+        let d: Vec<T> = (0..3).map(|i| p[i] - self.translation[i]).collect();
+        // the inverse of a rotation is its transpose.
+        Point::new_from_vec(
+            &(0..3)
+                .map(|c| {
+                    self.rotation[0][c] * d[0]
+                        + self.rotation[1][c] * d[1]
+                        + self.rotation[2][c] * d[2]
+                })
+                .collect(),
+        )
+    }
+
+    /// Project a world point to the image. Returns `None` when the point is not in front of the
+    /// camera.
+    /// # Panic
+    /// This function will panic if the point is not 3-dimensional.
+    pub fn project(&self, world: &Point<T>) -> Option<Point<T>> {
+        let camera = self.to_camera(world);
+        let (x, y, z) = (*camera.x(), *camera.y(), *camera.z());
+        if z <= T::zero() {
+            return None;
+        }
+        Some(Point::new_from_vec(&vec![
+            self.fx * x / z + self.cx,
+            self.fy * y / z + self.cy,
+        ]))
+    }
+
+    /// The world point that is projected to the image point, in the given depth (distance along
+    /// the camera's z axis).
+    /// # Panic
+    /// This function will panic if the image point is not 2-dimensional.
+    pub fn unproject(&self, pixel: &Point<T>, depth: T) -> Point<T> {
+        if pixel.get_size() != 2 {
+            panic!(
+                "expected a point with 2 dimensions, found {}",
+                pixel.get_size()
+            );
+        }
+        let x = (*pixel.x() - self.cx) / self.fx * depth;
+        let y = (*pixel.y() - self.cy) / self.fy * depth;
+        self.to_world(&Point::new_from_vec(&vec![x, y, depth]))
+    }
+}
@@ -0,0 +1,429 @@
+//! Random point generation, with samplers implementing `rand`'s `Distribution` trait.
+//!
+//! Every sampler works with any `Rng`, so seeding the generator (e.g. `StdRng::seed_from_u64`)
+//! makes the generated points reproducible.
+use crate::Point;
+use num::Float;
+use rand::distributions::Distribution;
+use rand::Rng;
+
+fn to_float<T: Float>(value: f64) -> T {
+    T::from(value).unwrap()
+}
+
+/// A standard normal value, using the Box-Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // 1 - [0, 1) is in (0, 1], so the logarithm is finite.
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn standard_normal_point<T: Float, R: Rng + ?Sized>(rng: &mut R, dim: usize) -> Point<T> {
+    Point::new_from_vec(&(0..dim).map(|_| to_float(standard_normal(rng))).collect())
+}
+
+/// `center + direction * scale`.
+fn offset<T: Float>(center: &Point<T>, direction: &Point<T>, scale: T) -> Point<T> {
+    let values = center
+        .get_vector()
+        .iter()
+        .zip(direction.get_vector())
+        .map(|(c, d)| *c + *d * scale)
+        .collect();
+    Point::new_from_vec(&values)
+}
+
+/// A uniform direction (a point on the unit sphere).
+fn unit_direction<T: Float, R: Rng + ?Sized>(rng: &mut R, dim: usize) -> Point<T> {
+    loop {
+        let p = standard_normal_point::<T, R>(rng, dim);
+        let norm = p.norm();
+        if norm > T::zero() {
+            return offset(
+                &Point::new_from_vec(&vec![T::zero(); dim]),
+                &p,
+                norm.recip(),
+            );
+        }
+    }
+}
+
+/// Check the center and the radius of a ball or a sphere (there are no directions in 0
+/// dimensions).
+fn check_ball<T: Float>(center: &Point<T>, radius: T) {
+    if center.get_size() == 0 {
+        panic!("the center has no dimensions");
+    }
+    if !radius.is_finite() || radius < T::zero() {
+        panic!("the radius is negative or not finite");
+    }
+}
+
+/// Points distributed uniformly in an axis-aligned box.
+/// # Example
+/// ```
+/// use multi_dim_point::random::UniformBox;
+/// use multi_dim_point::Point;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let min = Point::new_from_vec(&vec![0.0, -1.0]);
+/// let max = Point::new_from_vec(&vec![1.0, 1.0]);
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let p = UniformBox::new(&min, &max).sample(&mut rng);
+/// assert!(p.get_vector().iter().zip(min.get_vector()).all(|(v, m)| v >= m));
+/// assert!(p.get_vector().iter().zip(max.get_vector()).all(|(v, m)| v <= m));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniformBox<T> {
+    min: Point<T>,
+    max: Point<T>,
+}
+
+impl<T> UniformBox<T>
+where
+    T: Float,
+{
+    /// Creates a sampler in the box between the two corners.
+    /// # Panic
+    /// This function will panic if the dimensions of the corners are not equal, or if a value
+    /// in `min` is greater than the value in `max`.
+    pub fn new(min: &Point<T>, max: &Point<T>) -> UniformBox<T> {
+        if min.get_size() != max.get_size() {
+            panic!("dimensions are not equal");
+        }
+        if min
+            .get_vector()
+            .iter()
+            .zip(max.get_vector())
+            .any(|(a, b)| a > b)
+        {
+            panic!("the minimum corner is greater than the maximum corner");
+        }
+        UniformBox {
+            min: min.clone(),
+            max: max.clone(),
+        }
+    }
+}
+
+impl<T> Distribution<Point<T>> for UniformBox<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        let values = self
+            .min
+            .get_vector()
+            .iter()
+            .zip(self.max.get_vector())
+            .map(|(a, b)| *a + (*b - *a) * to_float(rng.gen::<f64>()))
+            .collect();
+        Point::new_from_vec(&values)
+    }
+}
+
+/// Points distributed uniformly in a ball (including its inside).
+/// # Example
+/// ```
+/// use multi_dim_point::random::UniformBall;
+/// use multi_dim_point::Point;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let center = Point::new_from_vec(&vec![1.0, 2.0, 3.0]);
+/// let ball = UniformBall::new(&center, 0.5);
+/// let mut rng = StdRng::seed_from_u64(7);
+/// assert!(ball.sample_iter(&mut rng).take(100).all(|p| p.distance(&center) <= 0.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniformBall<T> {
+    center: Point<T>,
+    radius: T,
+}
+
+impl<T> UniformBall<T>
+where
+    T: Float,
+{
+    /// Creates a sampler in the ball with the center and radius.
+    /// # Panic
+    /// This function will panic if the center has no dimensions, or if the radius is negative
+    /// or not finite.
+    pub fn new(center: &Point<T>, radius: T) -> UniformBall<T> {
+        check_ball(center, radius);
+        UniformBall {
+            center: center.clone(),
+            radius,
+        }
+    }
+}
+
+impl<T> Distribution<Point<T>> for UniformBall<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        let dim = self.center.get_size();
+        let direction = unit_direction::<T, R>(rng, dim);
+        // the volume inside radius r grows as r^dim.
+        let r = self.radius * to_float::<T>(rng.gen::<f64>().powf(1.0 / dim as f64));
+        offset(&self.center, &direction, r)
+    }
+}
+
+/// Points distributed uniformly on the surface of a sphere.
+/// # Example
+/// ```
+/// use multi_dim_point::random::UniformSphere;
+/// use multi_dim_point::Point;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let center: Point<f64> = Point::new_from_vec(&vec![0.0, 0.0, 0.0]);
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let p = UniformSphere::new(&center, 2.0).sample(&mut rng);
+/// assert!((p.norm() - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniformSphere<T> {
+    center: Point<T>,
+    radius: T,
+}
+
+impl<T> UniformSphere<T>
+where
+    T: Float,
+{
+    /// Creates a sampler on the sphere with the center and radius.
+    /// # Panic
+    /// This function will panic if the center has no dimensions, or if the radius is negative
+    /// or not finite.
+    pub fn new(center: &Point<T>, radius: T) -> UniformSphere<T> {
+        check_ball(center, radius);
+        UniformSphere {
+            center: center.clone(),
+            radius,
+        }
+    }
+}
+
+impl<T> Distribution<Point<T>> for UniformSphere<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        let direction = unit_direction::<T, R>(rng, self.center.get_size());
+        offset(&self.center, &direction, self.radius)
+    }
+}
+
+/// Points from an isotropic Gaussian (the same standard deviation in every dimension).
+/// # Example
+/// ```
+/// use multi_dim_point::random::Gaussian;
+/// use multi_dim_point::Point;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let gaussian = Gaussian::new(&Point::new_from_vec(&vec![10.0, 20.0]), 0.1);
+/// let mut rng = StdRng::seed_from_u64(7);
+/// assert_eq!(gaussian.sample(&mut rng).get_size(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gaussian<T> {
+    mean: Point<T>,
+    std_dev: T,
+}
+
+impl<T> Gaussian<T>
+where
+    T: Float,
+{
+    /// Creates a Gaussian sampler with the mean and standard deviation.
+    /// # Panic
+    /// This function will panic if the standard deviation is negative or not finite.
+    pub fn new(mean: &Point<T>, std_dev: T) -> Gaussian<T> {
+        if !std_dev.is_finite() || std_dev < T::zero() {
+            panic!("the standard deviation is negative or not finite");
+        }
+        Gaussian {
+            mean: mean.clone(),
+            std_dev,
+        }
+    }
+}
+
+impl<T> Distribution<Point<T>> for Gaussian<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        let noise = standard_normal_point::<T, R>(rng, self.mean.get_size());
+        offset(&self.mean, &noise, self.std_dev)
+    }
+}
+
+/// Points from a Gaussian with a full covariance matrix.
+/// # Example
+/// ```
+/// use multi_dim_point::random::MultivariateGaussian;
+/// use multi_dim_point::Point;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let mean = Point::new_from_vec(&vec![0.0, 0.0]);
+/// let covariance = vec![vec![1.0, 0.9], vec![0.9, 1.0]];
+/// let gaussian = MultivariateGaussian::new(&mean, &covariance).unwrap();
+/// let mut rng = StdRng::seed_from_u64(7);
+/// assert_eq!(gaussian.sample(&mut rng).get_size(), 2);
+/// // not positive definite
+/// assert!(MultivariateGaussian::new(&mean, &[vec![1.0, 2.0], vec![2.0, 1.0]]).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultivariateGaussian<T> {
+    mean: Point<T>,
+    /// The lower triangular Cholesky factor of the covariance, row after row.
+    cholesky: Vec<Vec<T>>,
+}
+
+impl<T> MultivariateGaussian<T>
+where
+    T: Float,
+{
+    /// Creates a Gaussian sampler with the mean and covariance matrix (a vector of rows).
+    /// Returns `None` if the covariance matrix is not symmetric positive definite.
+    /// # Panic
+    /// This function will panic if the covariance matrix is not `N`x`N`, where `N` is the
+    /// dimension of the mean.
+    pub fn new(mean: &Point<T>, covariance: &[Vec<T>]) -> Option<MultivariateGaussian<T>> {
+        let dim = mean.get_size();
+        if covariance.len() != dim || covariance.iter().any(|row| row.len() != dim) {
+            panic!("the covariance matrix must be {}x{}", dim, dim);
+        }
+        let tolerance = to_float::<T>(1e-9);
+        let mut cholesky = vec![vec![T::zero(); dim]; dim];
+        for i in 0..dim {
+            for j in 0..=i {
+                if (covariance[i][j] - covariance[j][i]).abs()
+                    > tolerance * (T::one() + covariance[i][j].abs())
+                {
+                    return None;
+                }
+                let sum = (0..j).fold(covariance[i][j], |sum, k| {
+                    sum - cholesky[i][k] * cholesky[j][k]
+                });
+                if i == j {
+                    if sum <= T::zero() || sum.is_nan() {
+                        return None;
+                    }
+                    cholesky[i][i] = sum.sqrt();
+                } else {
+                    cholesky[i][j] = sum / cholesky[j][j];
+                }
+            }
+        }
+        Some(MultivariateGaussian {
+            mean: mean.clone(),
+            cholesky,
+        })
+    }
+}
+
+impl<T> Distribution<Point<T>> for MultivariateGaussian<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        let noise = standard_normal_point::<T, R>(rng, self.mean.get_size());
+        let z = noise.get_vector();
+        let values = self
+            .mean
+            .get_vector()
+            .iter()
+            .zip(&self.cholesky)
+            .map(|(m, row)| row.iter().zip(z).fold(*m, |sum, (l, v)| sum + *l * *v))
+            .collect();
+        Point::new_from_vec(&values)
+    }
+}
+
+/// Points distributed uniformly in a simplex (a segment, triangle, tetrahedron, ...).
+/// # Example
+/// ```
+/// use multi_dim_point::random::UniformSimplex;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let p = UniformSimplex::<f64>::standard(3).sample(&mut rng);
+/// assert!((p.get_vector().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniformSimplex<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T> UniformSimplex<T>
+where
+    T: Float,
+{
+    /// Creates a sampler in the simplex with the vertices.
+    /// # Panic
+    /// This function will panic if there are no vertices, or if the dimensions of the vertices
+    /// are not equal.
+    pub fn new(vertices: &[Point<T>]) -> UniformSimplex<T> {
+        let first = match vertices.first() {
+            Some(first) => first,
+            None => panic!("a simplex needs at least one vertex"),
+        };
+        if vertices.iter().any(|v| v.get_size() != first.get_size()) {
+            panic!("dimensions are not equal");
+        }
+        UniformSimplex {
+            vertices: vertices.to_vec(),
+        }
+    }
+
+    /// The standard simplex in `dim` dimensions: points with non-negative values that sum to 1.
+    /// # Panic
+    /// This function will panic if `dim` is 0.
+    pub fn standard(dim: usize) -> UniformSimplex<T> {
+        if dim == 0 {
+            panic!("the standard simplex needs at least one dimension");
+        }
+        let vertices: Vec<Point<T>> = (0..dim)
+            .map(|i| {
+                let mut p = Point::new_from_vec(&vec![T::zero(); dim]);
+                p.set_value(i + 1, &T::one());
+                p
+            })
+            .collect();
+        UniformSimplex::new(&vertices)
+    }
+}
+
+impl<T> Distribution<Point<T>> for UniformSimplex<T>
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<T> {
+        // normalized exponential weights are uniform barycentric coordinates.
+        let weights: Vec<f64> = self
+            .vertices
+            .iter()
+            .map(|_| -(1.0 - rng.gen::<f64>()).ln())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let dim = self.vertices[0].get_size();
+        let mut values = vec![T::zero(); dim];
+        for (vertex, weight) in self.vertices.iter().zip(&weights) {
+            let w = to_float::<T>(weight / total);
+            for (value, v) in values.iter_mut().zip(vertex.get_vector()) {
+                *value = *value + *v * w;
+            }
+        }
+        Point::new_from_vec(&values)
+    }
+}
@@ -0,0 +1,176 @@
+//! Named axes of points.
+use crate::Point;
+use std::sync::Arc;
+
+/// The names of the axes of points, shared (by an `Arc`) between points with the same meaning
+/// of dimensions.
+///
+/// Points with different schemas can't be combined: `Add`, `Sub`, `apply_func` (and everything
+/// that uses it) panic when both points have a schema and the schemas are not equal. A point
+/// without a schema can be combined with any point, and the result takes the schema of the
+/// points. The schema doesn't take part in `==`, which compares only the values.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::{Point, Schema};
+/// let schema = Schema::new(&["price", "volume"]);
+/// let p1: Point<f64> = Point::new_from_vec(&vec![10.5, 300.0]).with_schema(&schema);
+/// assert_eq!(p1.get_by_name("volume"), Some(&300.0));
+/// let p2 = &p1 + &Point::new_from_vec(&vec![0.5, 0.0]);
+/// assert_eq!(p2.get_by_name("price"), Some(&11.0));
+/// ```
+/// ```should_panic
+/// use multi_dim_point::{Point, Schema};
+/// let p1: Point<i32> = Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["a", "b"]));
+/// let p2: Point<i32> = Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["b", "a"]));
+/// let _ = &p1 + &p2;
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Schema {
+    names: Vec<String>,
+}
+
+impl Schema {
+    /// Creates a schema of the axis names, the first name is the name of dimension 1.
+    /// # Panic
+    /// This function will panic if a name appears more than once.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Arc<Schema> {
+        let names: Vec<String> = names.iter().map(|n| n.as_ref().to_string()).collect();
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                panic!("the axis name {:?} appears more than once", name);
+            }
+        }
+        Arc::new(Schema { names })
+    }
+
+    /// Return the axis names.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Return the number of axes.
+    pub fn get_size(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Return the dimension of the axis with the name (starts from 1, like
+    /// [`Point::get_value`]).
+    pub fn dimension_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name).map(|i| i + 1)
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Clone,
+{
+    /// Set the schema of the point.
+    /// # Panic
+    /// This function will panic if the number of axes in the schema is not the dimension of the
+    /// point.
+    pub fn with_schema(mut self, schema: &Arc<Schema>) -> Point<T> {
+        if schema.get_size() != self.get_size() {
+            panic!(
+                "the schema has {} axes, but the point has {} dimensions",
+                schema.get_size(),
+                self.get_size()
+            );
+        }
+        self.schema = Some(Arc::clone(schema));
+        self
+    }
+
+    /// Remove the schema of the point.
+    pub fn without_schema(mut self) -> Point<T> {
+        self.schema = None;
+        self
+    }
+
+    /// Return the schema of the point, if it has one.
+    pub fn schema(&self) -> Option<&Arc<Schema>> {
+        self.schema.as_ref()
+    }
+
+    /// Return the value of the axis with the name, or `None` if the point has no schema or the
+    /// schema has no such axis.
+    pub fn get_by_name(&self, name: &str) -> Option<&T> {
+        let dim = self.schema.as_ref()?.dimension_of(name)?;
+        Some(self.get_value(dim))
+    }
+
+    /// Change the value of the axis with the name.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::{Point, Schema};
+    /// let schema = Schema::new(&["price", "volume"]);
+    /// let mut p1: Point<f64> = Point::new(2).with_schema(&schema);
+    /// p1.set_by_name("volume", &7.0);
+    /// assert_eq!(p1.get_vector(), &vec![0.0, 7.0]);
+    /// ```
+    /// # Panic
+    /// This function will panic if the point has no schema, or the schema has no such axis.
+    pub fn set_by_name(&mut self, name: &str, new_val: &T) {
+        let dim = match self.schema.as_ref().and_then(|s| s.dimension_of(name)) {
+            Some(dim) => dim,
+            None => panic!("the point has no axis named {:?}", name),
+        };
+        self.set_value(dim, new_val);
+    }
+
+    /// The value in the first dimension.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::Point;
+    /// let p1: Point<i32> = Point::new_from_vec(&vec![1, 2, 3, 4]);
+    /// assert_eq!((p1.x(), p1.y(), p1.z(), p1.w()), (&1, &2, &3, &4));
+    /// ```
+    /// # Panic
+    /// This function will panic if the point has no such dimension.
+    pub fn x(&self) -> &T {
+        self.get_value(1)
+    }
+
+    /// The value in the second dimension.
+    /// # Panic
+    /// This function will panic if the point has no such dimension.
+    pub fn y(&self) -> &T {
+        self.get_value(2)
+    }
+
+    /// The value in the third dimension.
+    /// # Panic
+    /// This function will panic if the point has no such dimension.
+    pub fn z(&self) -> &T {
+        self.get_value(3)
+    }
+
+    /// The value in the fourth dimension.
+    /// # Panic
+    /// This function will panic if the point has no such dimension.
+    pub fn w(&self) -> &T {
+        self.get_value(4)
+    }
+
+    /// The schema of the result of combining the points.
+    /// # Panic
+    /// This function will panic if both points have schemas, and the schemas are not equal.
+    pub(crate) fn combined_schema(&self, other: &Point<T>) -> Option<Arc<Schema>> {
+        match (&self.schema, &other.schema) {
+            (Some(a), Some(b)) => {
+                if !Arc::ptr_eq(a, b) && a != b {
+                    panic!("schemas are not equal ({:?} != {:?})", a.names, b.names);
+                }
+                Some(Arc::clone(a))
+            }
+            (Some(s), None) | (None, Some(s)) => Some(Arc::clone(s)),
+            (None, None) => None,
+        }
+    }
+
+    /// Set the schema of a result point (which has the same dimension).
+    pub(crate) fn with_schema_of(mut self, schema: Option<Arc<Schema>>) -> Point<T> {
+        self.schema = schema;
+        self
+    }
+}
@@ -0,0 +1,378 @@
+//! Low-discrepancy (quasi-random) sequences of points in the unit hypercube `[0, 1)^N`.
+//!
+//! All the sequences are iterators of `Point<f64>`, and all of them start with their first
+//! point (index 0).
+//!
+//! [`Halton`] and [`Rd`] support any dimension. [`Sobol`] needs direction numbers for every
+//! dimension after the first: the built-in ones (from Joe and Kuo) cover up to
+//! [`Sobol::MAX_DIMENSION`] (21) dimensions, and higher dimensions need direction numbers from
+//! the caller (see [`Sobol::with_direction_numbers`]).
+use crate::Point;
+use std::error::Error;
+use std::fmt;
+
+/// The first `count` prime numbers.
+fn primes(count: usize) -> Vec<u64> {
+    let mut primes: Vec<u64> = Vec::with_capacity(count);
+    let mut candidate = 2;
+    while primes.len() < count {
+        if primes
+            .iter()
+            .take_while(|p| *p * *p <= candidate)
+            .all(|p| candidate % p != 0)
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// The SplitMix64 generator, used for deterministic scrambling without extra dependencies.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The Halton sequence: dimension `i` is the radical inverse of the index in the `i`th prime
+/// base.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::sequence::Halton;
+/// let points: Vec<_> = Halton::new(2).skip(1).take(3).collect();
+/// assert_eq!(points[0].get_vector(), &vec![0.5, 1.0 / 3.0]);
+/// assert_eq!(points[1].get_vector(), &vec![0.25, 2.0 / 3.0]);
+/// assert_eq!(points[2].get_vector(), &vec![0.75, 1.0 / 9.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Halton {
+    bases: Vec<u64>,
+    /// A permutation of the digits for each base, or `None` for the plain sequence.
+    permutations: Option<Vec<Vec<u64>>>,
+    index: u64,
+}
+
+impl Halton {
+    /// Creates the Halton sequence of `dim`-dimensional points.
+    pub fn new(dim: usize) -> Halton {
+        Halton {
+            bases: primes(dim),
+            permutations: None,
+            index: 0,
+        }
+    }
+
+    /// Creates a scrambled Halton sequence: the digits in each base are shuffled by a random
+    /// permutation (that keeps 0 in place), chosen deterministically from the seed. Scrambling
+    /// removes the correlation between dimensions with large bases.
+    /// # Example
+    /// ```
+    /// use multi_dim_point::sequence::Halton;
+    /// let a: Vec<_> = Halton::scrambled(10, 7).take(5).collect();
+    /// let b: Vec<_> = Halton::scrambled(10, 7).take(5).collect();
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn scrambled(dim: usize, seed: u64) -> Halton {
+        let mut state = seed;
+        let bases = primes(dim);
+        let permutations = bases
+            .iter()
+            .map(|base| {
+                let mut permutation: Vec<u64> = (0..*base).collect();
+                // Fisher-Yates on the non zero digits.
+                for i in (2..*base as usize).rev() {
+                    let j = 1 + (split_mix(&mut state) % i as u64) as usize;
+                    permutation.swap(i, j);
+                }
+                permutation
+            })
+            .collect();
+        Halton {
+            bases,
+            permutations: Some(permutations),
+            index: 0,
+        }
+    }
+
+    fn radical_inverse(&self, dim: usize, mut index: u64) -> f64 {
+        let base = self.bases[dim];
+        let inv_base = 1.0 / base as f64;
+        let mut factor = inv_base;
+        let mut result = 0.0;
+        while index > 0 {
+            let mut digit = index % base;
+            if let Some(permutations) = &self.permutations {
+                digit = permutations[dim][digit as usize];
+            }
+            result += digit as f64 * factor;
+            index /= base;
+            factor *= inv_base;
+        }
+        result
+    }
+}
+
+impl Iterator for Halton {
+    type Item = Point<f64>;
+
+    fn next(&mut self) -> Option<Point<f64>> {
+        let values = (0..self.bases.len())
+            .map(|dim| self.radical_inverse(dim, self.index))
+            .collect();
+        self.index = self.index.checked_add(1)?;
+        Some(Point::new_from_vec(&values))
+    }
+}
+
+/// The direction numbers of one dimension of a Sobol sequence, in the format of Joe and Kuo:
+/// the degree `s` of the primitive polynomial, its coefficients `a` (as bits) and the initial
+/// odd numbers `m` (`s` of them).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionNumbers {
+    pub s: u32,
+    pub a: u32,
+    pub m: Vec<u32>,
+}
+
+/// The direction numbers of dimensions 2 to 21 from Joe and Kuo (new-joe-kuo-6.21201).
+const JOE_KUO: [(u32, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+const SOBOL_BITS: usize = 32;
+
+/// Error returned when creating a Sobol sequence fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SobolError {
+    /// There are more dimensions than the built-in direction numbers support.
+    TooManyDimensions { max: usize, found: usize },
+    /// There are fewer direction numbers than dimensions after the first.
+    MissingDirectionNumbers { expected: usize, found: usize },
+    /// The direction numbers of the dimension (starts from 2) are not valid: the degree must be
+    /// between 1 and 32, with `s` initial numbers, where `m[i]` is odd and less than `2^(i+1)`.
+    InvalidDirectionNumbers { dimension: usize },
+}
+
+impl fmt::Display for SobolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SobolError::TooManyDimensions { max, found } => write!(
+                f,
+                "the built-in direction numbers support up to {} dimensions, found {}",
+                max, found
+            ),
+            SobolError::MissingDirectionNumbers { expected, found } => write!(
+                f,
+                "expected {} direction numbers, found {}",
+                expected, found
+            ),
+            SobolError::InvalidDirectionNumbers { dimension } => {
+                write!(f, "invalid direction numbers for dimension {}", dimension)
+            }
+        }
+    }
+}
+
+impl Error for SobolError {}
+
+/// The Sobol sequence, generated in Gray code order. It has up to 2^32 points.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::sequence::Sobol;
+/// let points: Vec<_> = Sobol::new(2).unwrap().take(4).collect();
+/// assert_eq!(points[1].get_vector(), &vec![0.5, 0.5]);
+/// assert_eq!(points[2].get_vector(), &vec![0.75, 0.25]);
+/// assert_eq!(points[3].get_vector(), &vec![0.25, 0.75]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sobol {
+    /// `SOBOL_BITS` direction numbers for each dimension.
+    directions: Vec<[u32; SOBOL_BITS]>,
+    current: Vec<u32>,
+    index: u64,
+}
+
+impl Sobol {
+    /// The highest dimension supported by the built-in direction numbers.
+    pub const MAX_DIMENSION: usize = JOE_KUO.len() + 1;
+
+    /// Creates the Sobol sequence of `dim`-dimensional points, with the built-in direction
+    /// numbers.
+    /// # Errors
+    /// [`SobolError::TooManyDimensions`] is returned if `dim` is greater than
+    /// `Sobol::MAX_DIMENSION`; use `with_direction_numbers` for higher dimensions.
+    pub fn new(dim: usize) -> Result<Sobol, SobolError> {
+        if dim > Sobol::MAX_DIMENSION {
+            return Err(SobolError::TooManyDimensions {
+                max: Sobol::MAX_DIMENSION,
+                found: dim,
+            });
+        }
+        let numbers: Vec<DirectionNumbers> = JOE_KUO
+            .iter()
+            .take(dim.saturating_sub(1))
+            .map(|(s, a, m)| DirectionNumbers {
+                s: *s,
+                a: *a,
+                m: m.to_vec(),
+            })
+            .collect();
+        Sobol::with_direction_numbers(dim, &numbers)
+    }
+
+    /// Creates the Sobol sequence of `dim`-dimensional points, with direction numbers for the
+    /// dimensions after the first (which always uses the van der Corput sequence).
+    /// # Errors
+    /// [`SobolError::MissingDirectionNumbers`] is returned if there are fewer than `dim - 1`
+    /// direction numbers, and [`SobolError::InvalidDirectionNumbers`] if any of them is not
+    /// valid.
+    pub fn with_direction_numbers(
+        dim: usize,
+        numbers: &[DirectionNumbers],
+    ) -> Result<Sobol, SobolError> {
+        if numbers.len() + 1 < dim {
+            return Err(SobolError::MissingDirectionNumbers {
+                expected: dim - 1,
+                found: numbers.len(),
+            });
+        }
+        let mut directions = Vec::with_capacity(dim);
+        if dim > 0 {
+            let mut first = [0; SOBOL_BITS];
+            for (i, v) in first.iter_mut().enumerate() {
+                *v = 1 << (SOBOL_BITS - 1 - i);
+            }
+            directions.push(first);
+        }
+        for (d, n) in numbers.iter().take(dim.saturating_sub(1)).enumerate() {
+            let s = n.s as usize;
+            let valid = (1..=SOBOL_BITS).contains(&s)
+                && n.m.len() == s
+                && n.m
+                    .iter()
+                    .enumerate()
+                    .all(|(i, m)| m % 2 == 1 && u64::from(*m) < 1 << (i + 1));
+            if !valid {
+                return Err(SobolError::InvalidDirectionNumbers { dimension: d + 2 });
+            }
+            let mut v = [0u32; SOBOL_BITS];
+            for i in 0..SOBOL_BITS {
+                v[i] = if i < s {
+                    n.m[i] << (SOBOL_BITS - 1 - i)
+                } else {
+                    let mut value = v[i - s] ^ (v[i - s] >> s);
+                    for k in 1..s {
+                        if (n.a >> (s - 1 - k)) & 1 == 1 {
+                            value ^= v[i - k];
+                        }
+                    }
+                    value
+                };
+            }
+            directions.push(v);
+        }
+        Ok(Sobol {
+            current: vec![0; directions.len()],
+            directions,
+            index: 0,
+        })
+    }
+}
+
+impl Iterator for Sobol {
+    type Item = Point<f64>;
+
+    fn next(&mut self) -> Option<Point<f64>> {
+        if self.index >> SOBOL_BITS != 0 {
+            return None;
+        }
+        let scale = 1.0 / (1u64 << SOBOL_BITS) as f64;
+        let point = Point::new_from_vec(&self.current.iter().map(|x| *x as f64 * scale).collect());
+        // the next point in Gray code order flips the direction of the lowest zero bit.
+        let bit = (!self.index).trailing_zeros() as usize;
+        if bit < SOBOL_BITS {
+            for (x, v) in self.current.iter_mut().zip(&self.directions) {
+                *x ^= v[bit];
+            }
+        }
+        self.index += 1;
+        Some(point)
+    }
+}
+
+/// The R_d sequence of Roberts: additive recurrence with the generalized golden ratio.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::sequence::Rd;
+/// let golden = (1.0 + 5f64.sqrt()) / 2.0;
+/// let p = Rd::new(1).nth(1).unwrap();
+/// assert!((p.get_value(1) - (0.5 + 1.0 / golden).fract()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rd {
+    alpha: Vec<f64>,
+    seed: f64,
+    index: u64,
+}
+
+impl Rd {
+    /// Creates the R_d sequence of `dim`-dimensional points, starting at `(0.5, ..., 0.5)`.
+    pub fn new(dim: usize) -> Rd {
+        Rd::with_seed(dim, 0.5)
+    }
+
+    /// Creates the R_d sequence of `dim`-dimensional points, starting at `(seed, ..., seed)`.
+    pub fn with_seed(dim: usize, seed: f64) -> Rd {
+        // phi is the positive root of x^(dim+1) = x + 1.
+        let mut phi = 2.0f64;
+        for _ in 0..64 {
+            phi = (1.0 + phi).powf(1.0 / (dim as f64 + 1.0));
+        }
+        let alpha = (1..=dim).map(|i| phi.powi(-(i as i32)).fract()).collect();
+        Rd {
+            alpha,
+            seed,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for Rd {
+    type Item = Point<f64>;
+
+    fn next(&mut self) -> Option<Point<f64>> {
+        let n = self.index as f64;
+        let values = self
+            .alpha
+            .iter()
+            .map(|a| (self.seed + n * a).fract())
+            .collect();
+        self.index = self.index.checked_add(1)?;
+        Some(Point::new_from_vec(&values))
+    }
+}
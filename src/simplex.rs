@@ -0,0 +1,277 @@
+//! N-simplices (segments, triangles, tetrahedra, ...) in N-dimensional space.
+use crate::linalg::{determinant, solve};
+use crate::Point;
+use num::Float;
+use std::ops::{Add, Mul};
+
+/// An N-simplex: the convex hull of N+1 points in N dimensions.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::simplex::Simplex;
+/// use multi_dim_point::Point;
+/// let triangle = Simplex::new(&[
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![4.0, 0.0]),
+///     Point::new_from_vec(&vec![0.0, 3.0]),
+/// ]);
+/// assert_eq!(triangle.signed_volume(), 6.0);
+/// assert_eq!(triangle.circumcenter().unwrap().get_vector(), &vec![2.0, 1.5]);
+/// assert_eq!(triangle.circumradius(), Some(2.5));
+/// assert!(triangle.incenter().close(&Point::new_from_vec(&vec![1.0, 1.0]), 1e-12));
+/// let p = Point::new_from_vec(&vec![1.0, 1.0]);
+/// assert!(triangle.contains(&p));
+/// // linear interpolation of values at the vertices
+/// assert_eq!(triangle.interpolate(&p, &[0.0, 4.0, 3.0]), Some(2.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simplex<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T> Simplex<T>
+where
+    T: Float,
+{
+    /// Creates a simplex from its vertices.
+    /// # Panic
+    /// This function will panic if there are not N+1 vertices, where N is their dimension, or if
+    /// the dimensions of the vertices are not equal.
+    pub fn new(vertices: &[Point<T>]) -> Simplex<T> {
+        let dim = match vertices.first() {
+            Some(first) => first.get_size(),
+            None => panic!("a simplex needs at least one vertex"),
+        };
+        if vertices.iter().any(|v| v.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        if vertices.len() != dim + 1 {
+            panic!(
+                "a simplex in {} dimensions has {} vertices, found {}",
+                dim,
+                dim + 1,
+                vertices.len()
+            );
+        }
+        Simplex {
+            vertices: vertices.to_vec(),
+        }
+    }
+
+    /// The vertices of the simplex.
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+
+    /// The dimension of the simplex.
+    pub fn get_size(&self) -> usize {
+        self.vertices.len() - 1
+    }
+
+    /// The edges from the first vertex to the others, as rows.
+    fn edges(&self) -> Vec<Vec<T>> {
+        let origin = self.vertices[0].get_vector();
+        self.vertices[1..]
+            .iter()
+            .map(|v| {
+                v.get_vector()
+                    .iter()
+                    .zip(origin)
+                    .map(|(a, b)| *a - *b)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn factorial(n: usize) -> T {
+        (1..=n).fold(T::one(), |f, i| f * T::from(i).unwrap())
+    }
+
+    /// The signed volume (length, area, ...): the determinant of the rows `v_i - v_N` divided
+    /// by N!. It is positive if the vertices are positively oriented, like the simplices of a
+    /// [`Delaunay`](crate::delaunay::Delaunay) triangulation (counterclockwise in 2D).
+    pub fn signed_volume(&self) -> T {
+        let (last, rest) = self.vertices.split_last().unwrap();
+        let rows: Vec<Vec<T>> = rest
+            .iter()
+            .map(|v| {
+                v.get_vector()
+                    .iter()
+                    .zip(last.get_vector())
+                    .map(|(a, b)| *a - *b)
+                    .collect()
+            })
+            .collect();
+        determinant(&rows) / Simplex::<T>::factorial(self.get_size())
+    }
+
+    /// The volume (length, area, ...).
+    pub fn volume(&self) -> T {
+        self.signed_volume().abs()
+    }
+
+    /// The barycentric coordinates of the point: the N+1 weights of the vertices that sum to 1
+    /// and whose weighted sum is the point. `None` if the simplex is degenerate.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the simplex are not equal.
+    pub fn barycentric(&self, point: &Point<T>) -> Option<Vec<T>> {
+        if point.get_size() != self.get_size() {
+            panic!("dimensions are not equal");
+        }
+        // solve sum(w_i * (v_i - v_0)) = p - v_0 for the weights of all the vertices but the
+        // first.
+        let edges = self.edges();
+        let n = self.get_size();
+        let columns: Vec<Vec<T>> = (0..n)
+            .map(|row| edges.iter().map(|e| e[row]).collect())
+            .collect();
+        let rhs: Vec<T> = point
+            .get_vector()
+            .iter()
+            .zip(self.vertices[0].get_vector())
+            .map(|(p, o)| *p - *o)
+            .collect();
+        let weights = solve(&columns, &rhs)?;
+        let first = weights.iter().fold(T::one(), |s, w| s - *w);
+        let mut coordinates = vec![first];
+        coordinates.extend(weights);
+        Some(coordinates)
+    }
+
+    /// The point with the barycentric coordinates.
+    /// # Panic
+    /// This function will panic if there are not N+1 coordinates.
+    pub fn from_barycentric(&self, coordinates: &[T]) -> Point<T> {
+        if coordinates.len() != self.vertices.len() {
+            panic!(
+                "expected {} barycentric coordinates, found {}",
+                self.vertices.len(),
+                coordinates.len()
+            );
+        }
+        let mut values = vec![T::zero(); self.get_size()];
+        for (v, w) in self.vertices.iter().zip(coordinates) {
+            for (value, x) in values.iter_mut().zip(v.get_vector()) {
+                *value = *value + *x * *w;
+            }
+        }
+        Point::new_from_vec(&values)
+    }
+
+    /// Returns `true` if the point is in the simplex (including its boundary, up to rounding
+    /// errors). A degenerate simplex contains no points.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the simplex are not equal.
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        let tolerance = T::epsilon() * T::from(16).unwrap();
+        match self.barycentric(point) {
+            Some(coordinates) => coordinates.iter().all(|c| *c >= -tolerance),
+            None => false,
+        }
+    }
+
+    /// The center of the sphere through all the vertices. `None` if the simplex is degenerate.
+    pub fn circumcenter(&self) -> Option<Point<T>> {
+        // 2 (v_i - v_0) . (c - v_0) = |v_i - v_0|^2
+        let edges = self.edges();
+        let two = T::one() + T::one();
+        let matrix: Vec<Vec<T>> = edges
+            .iter()
+            .map(|e| e.iter().map(|v| *v * two).collect())
+            .collect();
+        let rhs: Vec<T> = edges
+            .iter()
+            .map(|e| e.iter().fold(T::zero(), |s, v| s + *v * *v))
+            .collect();
+        let offset = solve(&matrix, &rhs)?;
+        Some(Point::new_from_vec(
+            &offset
+                .iter()
+                .zip(self.vertices[0].get_vector())
+                .map(|(d, o)| *o + *d)
+                .collect(),
+        ))
+    }
+
+    /// The radius of the sphere through all the vertices. `None` if the simplex is degenerate.
+    pub fn circumradius(&self) -> Option<T> {
+        Some(self.circumcenter()?.distance(&self.vertices[0]))
+    }
+
+    /// The volumes of the facets, where facet `i` is opposite vertex `i`.
+    pub fn facet_volumes(&self) -> Vec<T> {
+        let n = self.get_size();
+        (0..self.vertices.len())
+            .map(|skip| {
+                let facet: Vec<&Point<T>> = self
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != skip)
+                    .map(|(_, v)| v)
+                    .collect();
+                // the square root of the Gram determinant of the facet's edges.
+                let edges: Vec<Vec<T>> = facet[1..]
+                    .iter()
+                    .map(|v| {
+                        v.get_vector()
+                            .iter()
+                            .zip(facet[0].get_vector())
+                            .map(|(a, b)| *a - *b)
+                            .collect()
+                    })
+                    .collect();
+                if edges.is_empty() {
+                    return T::one();
+                }
+                let gram: Vec<Vec<T>> = edges
+                    .iter()
+                    .map(|a| {
+                        edges
+                            .iter()
+                            .map(|b| a.iter().zip(b).fold(T::zero(), |s, (x, y)| s + *x * *y))
+                            .collect()
+                    })
+                    .collect();
+                determinant(&gram).max(T::zero()).sqrt() / Simplex::<T>::factorial(n - 1)
+            })
+            .collect()
+    }
+
+    /// The center of the inscribed sphere: the average of the vertices, weighted by the volumes
+    /// of the opposite facets.
+    pub fn incenter(&self) -> Point<T> {
+        let weights = self.facet_volumes();
+        let total = weights.iter().fold(T::zero(), |s, w| s + *w);
+        let coordinates: Vec<T> = weights.iter().map(|w| *w / total).collect();
+        self.from_barycentric(&coordinates)
+    }
+
+    /// The radius of the inscribed sphere.
+    pub fn inradius(&self) -> T {
+        let total = self.facet_volumes().iter().fold(T::zero(), |s, w| s + *w);
+        T::from(self.get_size()).unwrap() * self.volume() / total
+    }
+
+    /// Interpolate linearly the values at the vertices (numbers, points, ...) at the point, by
+    /// its barycentric coordinates. `None` if the simplex is degenerate.
+    /// # Panic
+    /// This function will panic if there are not N+1 values, or if the dimensions of the point
+    /// and the simplex are not equal.
+    pub fn interpolate<V>(&self, point: &Point<T>, values: &[V]) -> Option<V>
+    where
+        V: Clone + Add<Output = V> + Mul<T, Output = V>,
+    {
+        if values.len() != self.vertices.len() {
+            panic!(
+                "expected {} values, found {}",
+                self.vertices.len(),
+                values.len()
+            );
+        }
+        let coordinates = self.barycentric(point)?;
+        let mut terms = values.iter().zip(coordinates).map(|(v, c)| v.clone() * c);
+        let first = terms.next().unwrap();
+        Some(terms.fold(first, |sum, term| sum + term))
+    }
+}
@@ -0,0 +1,272 @@
+//! Morton (Z-order) and Hilbert space-filling curves, mapping integer points to `u128` indices.
+//!
+//! Both curves interleave `bits` bits of every dimension, so a point of dimension `N` can be
+//! encoded when `N * bits <= 128` (and `bits` is at most the width of the value type).
+use crate::Point;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can be a coordinate of a space-filling curve. Implemented for
+/// `u32` and `u64`.
+pub trait CurveCoordinate: Copy + Default + private::Sealed {
+    /// The number of bits of the type.
+    const BITS: u32;
+    /// Convert the value to `u128`.
+    fn to_u128(self) -> u128;
+    /// Convert the value from `u128` (which must fit in `Self::BITS` bits).
+    fn from_u128(value: u128) -> Self;
+}
+
+macro_rules! impl_curve_coordinate {
+    ($($t:ty),*) => ($(
+        impl private::Sealed for $t {}
+        impl CurveCoordinate for $t {
+            const BITS: u32 = <$t>::BITS;
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+            fn from_u128(value: u128) -> Self {
+                value as $t
+            }
+        }
+    )*)
+}
+
+impl_curve_coordinate! { u32, u64 }
+
+/// The highest number of bits per dimension that fits in an index, for points of type `T`.
+fn max_bits<T: CurveCoordinate>(dim: usize) -> u32 {
+    if dim == 0 {
+        panic!("a point on a space-filling curve has at least one dimension");
+    }
+    T::BITS.min((128 / dim) as u32)
+}
+
+fn check_bits<T: CurveCoordinate>(dim: usize, bits: u32) {
+    if bits == 0 || bits > max_bits::<T>(dim) {
+        panic!(
+            "{} bits per dimension don't fit in an index of {} dimensions",
+            bits, dim
+        );
+    }
+}
+
+/// The values of the point as `u128`, or `None` if one of them doesn't fit in `bits` bits.
+fn coordinates<T: CurveCoordinate>(point: &Point<T>, bits: u32) -> Option<Vec<u128>> {
+    point
+        .get_vector()
+        .iter()
+        .map(|v| v.to_u128())
+        .map(|v| if v >> bits == 0 { Some(v) } else { None })
+        .collect()
+}
+
+/// Interleave the bits, from the most significant bit of the first value to the least
+/// significant bit of the last value.
+fn interleave(values: &[u128], bits: u32) -> u128 {
+    let mut index = 0;
+    for bit in (0..bits).rev() {
+        for v in values {
+            index = (index << 1) | ((v >> bit) & 1);
+        }
+    }
+    index
+}
+
+fn deinterleave(index: u128, dim: usize, bits: u32) -> Vec<u128> {
+    let mut values = vec![0; dim];
+    let mut position = dim as u32 * bits;
+    for bit in (0..bits).rev() {
+        for v in values.iter_mut() {
+            position -= 1;
+            *v |= ((index >> position) & 1) << bit;
+        }
+    }
+    values
+}
+
+/// Encode the point as its index on the Morton (Z-order) curve. Returns `None` if a value doesn't
+/// fit in the bits available for each dimension (`128 / N`, at most the width of `T`).
+/// # Example
+/// ```
+/// use multi_dim_point::space_filling::{morton_decode, morton_encode};
+/// use multi_dim_point::Point;
+/// let p: Point<u32> = Point::new_from_vec(&vec![3, 5]);
+/// // 3 = 011, 5 = 101 -> 01 10 11
+/// assert_eq!(morton_encode(&p), Some(0b011011));
+/// assert_eq!(morton_decode::<u32>(0b011011, 2), Some(p));
+/// ```
+/// # Panic
+/// This function will panic if the point has no dimensions.
+pub fn morton_encode<T: CurveCoordinate>(point: &Point<T>) -> Option<u128> {
+    let bits = max_bits::<T>(point.get_size());
+    Some(interleave(&coordinates(point, bits)?, bits))
+}
+
+/// Decode the `dim`-dimensional point at the index on the Morton curve. Returns `None` if the
+/// index is too large to be an index of a `dim`-dimensional point.
+/// # Panic
+/// This function will panic if `dim` is 0.
+pub fn morton_decode<T: CurveCoordinate>(index: u128, dim: usize) -> Option<Point<T>> {
+    let bits = max_bits::<T>(dim);
+    let total = dim as u32 * bits;
+    if total < 128 && index >> total != 0 {
+        return None;
+    }
+    let values = deinterleave(index, dim, bits);
+    Some(Point::new_from_vec(
+        &values.into_iter().map(T::from_u128).collect(),
+    ))
+}
+
+/// Encode the point as its index on the Hilbert curve of order `bits` (a grid of `2^bits`
+/// values in every dimension). Returns `None` if a value doesn't fit in `bits` bits.
+///
+/// Uses the transpose algorithm of J. Skilling ("Programming the Hilbert curve", 2004), which
+/// works in any dimension.
+/// # Example
+/// ```
+/// use multi_dim_point::space_filling::hilbert_encode;
+/// use multi_dim_point::Point;
+/// let order: Vec<u128> = [[0u32, 0], [0, 1], [1, 1], [1, 0]]
+///     .iter()
+///     .map(|v| hilbert_encode(&Point::new_from_vec(&v.to_vec()), 1).unwrap())
+///     .collect();
+/// assert_eq!(order, vec![0, 1, 2, 3]);
+/// ```
+/// # Panic
+/// This function will panic if the point has no dimensions, or if `bits` is 0 or doesn't fit
+/// (`N * bits > 128`, or `bits` is larger than the width of `T`).
+pub fn hilbert_encode<T: CurveCoordinate>(point: &Point<T>, bits: u32) -> Option<u128> {
+    let dim = point.get_size();
+    check_bits::<T>(dim, bits);
+    let mut x = coordinates(point, bits)?;
+    let m: u128 = 1 << (bits - 1);
+    // inverse undo
+    let mut q = m;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..dim {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+    // Gray encode
+    for i in 1..dim {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    let mut q = m;
+    while q > 1 {
+        if x[dim - 1] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    for v in x.iter_mut() {
+        *v ^= t;
+    }
+    Some(interleave(&x, bits))
+}
+
+/// Decode the `dim`-dimensional point at the index on the Hilbert curve of order `bits`.
+/// Returns `None` if the index is too large for the curve.
+/// # Example
+/// ```
+/// use multi_dim_point::space_filling::{hilbert_decode, hilbert_encode};
+/// use multi_dim_point::Point;
+/// let p: Point<u64> = Point::new_from_vec(&vec![5, 1, 7]);
+/// let index = hilbert_encode(&p, 3).unwrap();
+/// assert_eq!(hilbert_decode::<u64>(index, 3, 3), Some(p));
+/// ```
+/// # Panic
+/// This function will panic if `dim` is 0, or if `bits` is 0 or doesn't fit.
+pub fn hilbert_decode<T: CurveCoordinate>(index: u128, dim: usize, bits: u32) -> Option<Point<T>> {
+    check_bits::<T>(dim, bits);
+    let total = dim as u32 * bits;
+    if total < 128 && index >> total != 0 {
+        return None;
+    }
+    let mut x = deinterleave(index, dim, bits);
+    // Gray decode
+    let t = x[dim - 1] >> 1;
+    for i in (1..dim).rev() {
+        x[i] ^= x[i - 1];
+    }
+    x[0] ^= t;
+    // undo excess work
+    let n: u128 = 2 << (bits - 1);
+    let mut q = 2;
+    while q != n {
+        let p = q - 1;
+        for i in (0..dim).rev() {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q <<= 1;
+    }
+    Some(Point::new_from_vec(
+        &x.into_iter().map(T::from_u128).collect(),
+    ))
+}
+
+/// Sort the points by their order on the Morton curve.
+/// # Panic
+/// This function will panic if a point can't be encoded (see `morton_encode`), or if the
+/// dimensions of the points are not equal.
+pub fn sort_by_morton<T: CurveCoordinate>(points: &mut [Point<T>]) {
+    check_dimensions(points);
+    points.sort_by_cached_key(|p| match morton_encode(p) {
+        Some(index) => index,
+        None => panic!("the point doesn't fit in a Morton index"),
+    });
+}
+
+/// Sort the points by their order on the Hilbert curve, using the highest order that fits in an
+/// index (`128 / N` bits, at most the width of `T`).
+/// # Example
+/// ```
+/// use multi_dim_point::space_filling::sort_by_hilbert;
+/// use multi_dim_point::Point;
+/// let mut points: Vec<Point<u32>> = vec![
+///     Point::new_from_vec(&vec![3, 0]),
+///     Point::new_from_vec(&vec![0, 3]),
+///     Point::new_from_vec(&vec![0, 0]),
+/// ];
+/// sort_by_hilbert(&mut points);
+/// assert_eq!(points[0].get_vector(), &vec![0, 0]);
+/// ```
+/// # Panic
+/// This function will panic if a value doesn't fit, or if the dimensions of the points are not
+/// equal.
+pub fn sort_by_hilbert<T: CurveCoordinate>(points: &mut [Point<T>]) {
+    check_dimensions(points);
+    if let Some(first) = points.first() {
+        let bits = max_bits::<T>(first.get_size());
+        points.sort_by_cached_key(|p| match hilbert_encode(p, bits) {
+            Some(index) => index,
+            None => panic!("the point doesn't fit in a Hilbert index"),
+        });
+    }
+}
+
+fn check_dimensions<T: CurveCoordinate>(points: &[Point<T>]) {
+    if let Some(first) = points.first() {
+        if points.iter().any(|p| p.get_size() != first.get_size()) {
+            panic!("dimensions are not equal");
+        }
+    }
+}
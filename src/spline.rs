@@ -0,0 +1,564 @@
+//! Smooth curves through (or near) a sequence of points: Catmull-Rom, cubic B-spline and natural
+//! cubic spline, with arc-length parameterization and resampling for every [`Curve`].
+use crate::interpolate::Bezier;
+use crate::Point;
+use num::Float;
+
+/// A parametric curve of points.
+pub trait Curve<T>
+where
+    T: Float,
+{
+    /// The range of the parameter, `(start, end)`.
+    fn domain(&self) -> (T, T);
+
+    /// The point of the curve in parameter `t`.
+    fn eval(&self, t: T) -> Point<T>;
+
+    /// The tangent vector (first derivative by `t`) of the curve in parameter `t`.
+    fn tangent(&self, t: T) -> Point<T>;
+
+    /// The length of the curve (calculated numerically).
+    fn arc_length(&self) -> T
+    where
+        Self: Sized,
+    {
+        ArcLength::new(self).total()
+    }
+
+    /// Points along the curve, `step` apart (by arc length), from the start of the curve. The
+    /// end of the curve is always the last point, so the last step may be shorter.
+    /// # Panic
+    /// This function will panic if the step is not positive.
+    fn resample_by_distance(&self, step: T) -> Vec<Point<T>>
+    where
+        Self: Sized,
+    {
+        if step <= T::zero() || step.is_nan() {
+            panic!("the step must be positive");
+        }
+        let table = ArcLength::new(self);
+        let total = table.total();
+        let mut points = Vec::new();
+        let mut s = T::zero();
+        // leave out a sample that is (almost) the end point.
+        while s < total - step * T::from(1e-6).unwrap() {
+            points.push(table.point_at(s));
+            s = s + step;
+        }
+        points.push(self.eval(self.domain().1));
+        points
+    }
+
+    /// `count` points along the curve, equally spaced by arc length, including both ends.
+    /// # Panic
+    /// This function will panic if `count` is smaller than 2.
+    fn resample(&self, count: usize) -> Vec<Point<T>>
+    where
+        Self: Sized,
+    {
+        if count < 2 {
+            panic!("resampling needs at least 2 points ({} < 2)", count);
+        }
+        let table = ArcLength::new(self);
+        let n = T::from(count - 1).unwrap();
+        (0..count)
+            .map(|i| table.point_at(table.total() * T::from(i).unwrap() / n))
+            .collect()
+    }
+}
+
+/// Arc-length table of a curve, for moving along the curve by distance.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::interpolate::Bezier;
+/// use multi_dim_point::spline::{ArcLength, Curve};
+/// use multi_dim_point::Point;
+/// let line = Bezier::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![3.0, 3.0]),
+/// ]);
+/// let table = ArcLength::new(&line);
+/// assert!((table.total() - 18f64.sqrt()).abs() < 1e-9);
+/// let p = table.point_at(2f64.sqrt());
+/// assert!(p.close(&Point::new_from_vec(&vec![1.0, 1.0]), 1e-9));
+/// ```
+pub struct ArcLength<'a, T, C>
+where
+    T: Float,
+    C: Curve<T>,
+{
+    curve: &'a C,
+    params: Vec<T>,
+    lengths: Vec<T>,
+}
+
+/// Number of table intervals in each unit of the parameter.
+const INTERVALS_PER_UNIT: usize = 32;
+
+impl<'a, T, C> ArcLength<'a, T, C>
+where
+    T: Float,
+    C: Curve<T>,
+{
+    /// Build the table of the curve.
+    pub fn new(curve: &'a C) -> Self {
+        let (start, end) = curve.domain();
+        let units = (end - start).ceil().to_usize().unwrap_or(1).max(1);
+        let intervals = units * INTERVALS_PER_UNIT;
+        let n = T::from(intervals).unwrap();
+        let params: Vec<T> = (0..=intervals)
+            .map(|i| start + (end - start) * T::from(i).unwrap() / n)
+            .collect();
+        let mut lengths = Vec::with_capacity(params.len());
+        lengths.push(T::zero());
+        for pair in params.windows(2) {
+            let length = *lengths.last().unwrap() + gauss_legendre(curve, pair[0], pair[1]);
+            lengths.push(length);
+        }
+        ArcLength {
+            curve,
+            params,
+            lengths,
+        }
+    }
+
+    /// The total length of the curve.
+    pub fn total(&self) -> T {
+        *self.lengths.last().unwrap()
+    }
+
+    /// The parameter of the point in distance `s` from the start of the curve (clamped to the
+    /// curve). The start of the curve is returned when `s` or the length of the curve is NaN.
+    pub fn param_at(&self, s: T) -> T {
+        let last = self.lengths.len() - 1;
+        if s <= T::zero() || s.is_nan() || self.total().is_nan() {
+            return self.params[0];
+        }
+        if s >= self.total() {
+            return self.params[last];
+        }
+        // the lengths are not NaN and increase, so this is in `1..=last`.
+        let i = self.lengths.partition_point(|l| *l < s);
+        if self.lengths[i] == s {
+            return self.params[i];
+        }
+        let i = i - 1;
+        let (t0, t1) = (self.params[i], self.params[i + 1]);
+        let (s0, s1) = (self.lengths[i], self.lengths[i + 1]);
+        if s1 <= s0 {
+            return t0;
+        }
+        // start linear inside the interval, and refine with Newton's method.
+        let mut t = t0 + (t1 - t0) * (s - s0) / (s1 - s0);
+        for _ in 0..4 {
+            let speed = self.curve.tangent(t).norm();
+            if speed.is_zero() {
+                break;
+            }
+            let error = s0 + gauss_legendre(self.curve, t0, t) - s;
+            t = (t - error / speed).max(t0).min(t1);
+        }
+        t
+    }
+
+    /// The point in distance `s` from the start of the curve (clamped to the curve).
+    pub fn point_at(&self, s: T) -> Point<T> {
+        self.curve.eval(self.param_at(s))
+    }
+}
+
+/// The length of the curve between `a` and `b`, by 5 points Gauss-Legendre quadrature.
+fn gauss_legendre<T: Float, C: Curve<T>>(curve: &C, a: T, b: T) -> T {
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    ];
+    let half = (b - a) / T::from(2).unwrap();
+    let mid = (a + b) / T::from(2).unwrap();
+    NODES.iter().fold(T::zero(), |sum, (x, w)| {
+        let t = mid + half * T::from(*x).unwrap();
+        sum + T::from(*w).unwrap() * curve.tangent(t).norm()
+    }) * half
+}
+
+impl<T> Curve<T> for Bezier<T>
+where
+    T: Float,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::one())
+    }
+
+    fn eval(&self, t: T) -> Point<T> {
+        Bezier::eval(self, t)
+    }
+
+    fn tangent(&self, t: T) -> Point<T> {
+        Bezier::tangent(self, t)
+    }
+}
+
+fn check_points<T>(points: &[Point<T>], min: usize)
+where
+    T: Clone,
+{
+    if points.len() < min {
+        panic!(
+            "the curve needs at least {} points ({} < {})",
+            min,
+            points.len(),
+            min
+        );
+    }
+    let dim = points[0].get_size();
+    if points.iter().any(|p| p.get_size() != dim) {
+        panic!("dimensions are not equal");
+    }
+}
+
+/// The segment of parameter `t` (for curves where segment `i` is `[i, i + 1]`), and the local
+/// parameter in the segment.
+fn segment<T: Float>(t: T, segments: usize) -> (usize, T) {
+    let i = t
+        .floor()
+        .max(T::zero())
+        .to_usize()
+        .unwrap_or(0)
+        .min(segments - 1);
+    (i, t - T::from(i).unwrap())
+}
+
+/// `sum(weights[k] * points[k])` for 4 points.
+fn combine<T: Float>(points: [&Point<T>; 4], weights: [T; 4]) -> Point<T> {
+    let dim = points[0].get_size();
+    let values = (1..=dim)
+        .map(|d| {
+            (0..4).fold(T::zero(), |sum, k| {
+                sum + *points[k].get_value(d) * weights[k]
+            })
+        })
+        .collect();
+    Point::new_from_vec(&values)
+}
+
+/// Uniform Catmull-Rom spline, passing through all the points. The parameter of the curve is
+/// `[0, n - 1]`, where `t = i` is the point `i`. The missing neighbours of the end points are
+/// reflected (e.g. `P(-1) = 2 P(0) - P(1)`).
+///
+/// # Example
+/// ```
+/// use multi_dim_point::spline::{CatmullRom, Curve};
+/// use multi_dim_point::Point;
+/// let curve = CatmullRom::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![2.0, 0.0]),
+/// ]);
+/// assert_eq!(curve.domain(), (0.0, 2.0));
+/// assert_eq!(curve.eval(1.0).get_vector(), &vec![1.0, 1.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CatmullRom<T> {
+    points: Vec<Point<T>>,
+}
+
+impl<T> CatmullRom<T>
+where
+    T: Float,
+{
+    /// Creates the spline through the points.
+    /// # Panic
+    /// This function will panic when there are less than 2 points, or when their dimensions are
+    /// not equal.
+    pub fn new(points: Vec<Point<T>>) -> CatmullRom<T> {
+        check_points(&points, 2);
+        CatmullRom { points }
+    }
+
+    /// Return the points the spline passes through.
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// The 4 points that control segment `i`.
+    fn segment_points(&self, i: usize) -> [Point<T>; 4] {
+        let n = self.points.len();
+        let two = T::from(2).unwrap();
+        let before = if i == 0 {
+            Point::new_from_vec(&self.points[0].apply_func(&self.points[1], &|a, b| two * *a - *b))
+        } else {
+            self.points[i - 1].clone()
+        };
+        let after = if i + 2 >= n {
+            Point::new_from_vec(
+                &self.points[n - 1].apply_func(&self.points[n - 2], &|a, b| two * *a - *b),
+            )
+        } else {
+            self.points[i + 2].clone()
+        };
+        [
+            before,
+            self.points[i].clone(),
+            self.points[i + 1].clone(),
+            after,
+        ]
+    }
+}
+
+impl<T> Curve<T> for CatmullRom<T>
+where
+    T: Float,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::from(self.points.len() - 1).unwrap())
+    }
+
+    fn eval(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.points.len() - 1);
+        let [p0, p1, p2, p3] = self.segment_points(i);
+        let c = |x: f64| T::from(x).unwrap();
+        let (u2, u3) = (u * u, u * u * u);
+        let weights = [
+            c(0.5) * (-u + c(2.0) * u2 - u3),
+            c(0.5) * (c(2.0) - c(5.0) * u2 + c(3.0) * u3),
+            c(0.5) * (u + c(4.0) * u2 - c(3.0) * u3),
+            c(0.5) * (-u2 + u3),
+        ];
+        combine([&p0, &p1, &p2, &p3], weights)
+    }
+
+    fn tangent(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.points.len() - 1);
+        let [p0, p1, p2, p3] = self.segment_points(i);
+        let c = |x: f64| T::from(x).unwrap();
+        let u2 = u * u;
+        let weights = [
+            c(0.5) * (-T::one() + c(4.0) * u - c(3.0) * u2),
+            c(0.5) * (-c(10.0) * u + c(9.0) * u2),
+            c(0.5) * (T::one() + c(8.0) * u - c(9.0) * u2),
+            c(0.5) * (-c(2.0) * u + c(3.0) * u2),
+        ];
+        combine([&p0, &p1, &p2, &p3], weights)
+    }
+}
+
+/// Uniform cubic B-spline of control points. The curve is smooth (continuous second
+/// derivative) and doesn't pass through the control points, except the first and the last (the
+/// end points are repeated to clamp the curve to them). The parameter of the curve is
+/// `[0, n + 1]` for `n` control points.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::spline::{BSpline, Curve};
+/// use multi_dim_point::Point;
+/// let curve = BSpline::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![2.0, 0.0]),
+/// ]);
+/// assert!(curve.eval(0.0).close(&Point::new_from_vec(&vec![0.0, 0.0]), 1e-12));
+/// assert!(curve.eval(4.0).close(&Point::new_from_vec(&vec![2.0, 0.0]), 1e-12));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BSpline<T> {
+    control_points: Vec<Point<T>>,
+}
+
+impl<T> BSpline<T>
+where
+    T: Float,
+{
+    /// Creates the spline of the control points.
+    /// # Panic
+    /// This function will panic when there are less than 2 points, or when their dimensions are
+    /// not equal.
+    pub fn new(control_points: Vec<Point<T>>) -> BSpline<T> {
+        check_points(&control_points, 2);
+        BSpline { control_points }
+    }
+
+    /// Return the control points.
+    pub fn control_points(&self) -> &[Point<T>] {
+        &self.control_points
+    }
+
+    fn segments(&self) -> usize {
+        self.control_points.len() + 1
+    }
+
+    /// Control point `i` of the clamped sequence (the end points are repeated 3 times).
+    fn clamped(&self, i: usize) -> &Point<T> {
+        let n = self.control_points.len();
+        &self.control_points[i.saturating_sub(2).min(n - 1)]
+    }
+
+    fn segment_points(&self, i: usize) -> [&Point<T>; 4] {
+        [
+            self.clamped(i),
+            self.clamped(i + 1),
+            self.clamped(i + 2),
+            self.clamped(i + 3),
+        ]
+    }
+}
+
+impl<T> Curve<T> for BSpline<T>
+where
+    T: Float,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::from(self.segments()).unwrap())
+    }
+
+    fn eval(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.segments());
+        let c = |x: f64| T::from(x).unwrap();
+        let v = T::one() - u;
+        let (u2, u3) = (u * u, u * u * u);
+        let weights = [
+            v * v * v / c(6.0),
+            (c(3.0) * u3 - c(6.0) * u2 + c(4.0)) / c(6.0),
+            (-c(3.0) * u3 + c(3.0) * u2 + c(3.0) * u + T::one()) / c(6.0),
+            u3 / c(6.0),
+        ];
+        combine(self.segment_points(i), weights)
+    }
+
+    fn tangent(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.segments());
+        let c = |x: f64| T::from(x).unwrap();
+        let v = T::one() - u;
+        let u2 = u * u;
+        let weights = [
+            -v * v / c(2.0),
+            (c(3.0) * u2 - c(4.0) * u) / c(2.0),
+            (-c(3.0) * u2 + c(2.0) * u + T::one()) / c(2.0),
+            u2 / c(2.0),
+        ];
+        combine(self.segment_points(i), weights)
+    }
+}
+
+/// Natural cubic spline, passing through all the points with continuous first and second
+/// derivatives, and zero second derivative at the ends. The parameter of the curve is
+/// `[0, n - 1]`, where `t = i` is the point `i`.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::spline::{Curve, NaturalCubicSpline};
+/// use multi_dim_point::Point;
+/// let curve = NaturalCubicSpline::new(vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![2.0, 0.0]),
+/// ]);
+/// assert_eq!(curve.eval(1.0).get_vector(), &vec![1.0, 1.0]);
+/// assert!(curve.tangent(1.0).close(&Point::new_from_vec(&vec![1.0, 0.0]), 1e-12));
+/// ```
+#[derive(Debug, Clone)]
+pub struct NaturalCubicSpline<T> {
+    points: Vec<Point<T>>,
+    /// The second derivatives in the points.
+    second: Vec<Point<T>>,
+}
+
+impl<T> NaturalCubicSpline<T>
+where
+    T: Float,
+{
+    /// Creates the spline through the points.
+    /// # Panic
+    /// This function will panic when there are less than 2 points, or when their dimensions are
+    /// not equal.
+    pub fn new(points: Vec<Point<T>>) -> NaturalCubicSpline<T> {
+        check_points(&points, 2);
+        let n = points.len();
+        let dim = points[0].get_size();
+        let c = |x: f64| T::from(x).unwrap();
+        // solve M(i-1) + 4 M(i) + M(i+1) = 6 (y(i+1) - 2 y(i) + y(i-1)), M(0) = M(n-1) = 0, for
+        // every dimension, by the Thomas algorithm.
+        let mut second = vec![vec![T::zero(); dim]; n];
+        for d in 1..=dim {
+            let y: Vec<T> = points.iter().map(|p| *p.get_value(d)).collect();
+            let inner = n - 2;
+            let mut diag = vec![c(4.0); inner];
+            let mut rhs: Vec<T> = (1..n - 1)
+                .map(|i| c(6.0) * (y[i + 1] - c(2.0) * y[i] + y[i - 1]))
+                .collect();
+            for i in 1..inner {
+                let w = T::one() / diag[i - 1];
+                diag[i] = diag[i] - w;
+                rhs[i] = rhs[i] - w * rhs[i - 1];
+            }
+            for i in (0..inner).rev() {
+                let next = if i + 1 < inner {
+                    second[i + 2][d - 1]
+                } else {
+                    T::zero()
+                };
+                second[i + 1][d - 1] = (rhs[i] - next) / diag[i];
+            }
+        }
+        NaturalCubicSpline {
+            points,
+            second: second.iter().map(Point::new_from_vec).collect(),
+        }
+    }
+
+    /// Return the points the spline passes through.
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+}
+
+impl<T> Curve<T> for NaturalCubicSpline<T>
+where
+    T: Float,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::from(self.points.len() - 1).unwrap())
+    }
+
+    fn eval(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.points.len() - 1);
+        let c = |x: f64| T::from(x).unwrap();
+        let v = T::one() - u;
+        let weights = [v, u, (v * v * v - v) / c(6.0), (u * u * u - u) / c(6.0)];
+        combine(
+            [
+                &self.points[i],
+                &self.points[i + 1],
+                &self.second[i],
+                &self.second[i + 1],
+            ],
+            weights,
+        )
+    }
+
+    fn tangent(&self, t: T) -> Point<T> {
+        let (i, u) = segment(t, self.points.len() - 1);
+        let c = |x: f64| T::from(x).unwrap();
+        let v = T::one() - u;
+        let weights = [
+            -T::one(),
+            T::one(),
+            (T::one() - c(3.0) * v * v) / c(6.0),
+            (c(3.0) * u * u - T::one()) / c(6.0),
+        ];
+        combine(
+            [
+                &self.points[i],
+                &self.points[i + 1],
+                &self.second[i],
+                &self.second[i + 1],
+            ],
+            weights,
+        )
+    }
+}
@@ -0,0 +1,585 @@
+//! Descriptive statistics over sets of points.
+//!
+//! The moments (mean, variance and covariance) are accumulated with Welford's numerically stable
+//! algorithm by `Accumulator`; the functions taking iterators are shortcuts for it. Functions
+//! returning `Option` return `None` for an empty set of points.
+//!
+//! For data with outliers, the geometric median, the componentwise `median` and the
+//! `trimmed_mean` are robust estimators of the centre.
+use crate::Point;
+use num::Float;
+use std::cmp::Ordering;
+
+/// Running statistics of points: count, per-axis minimum and maximum, mean, variance and
+/// covariance, updated in one pass with Welford's algorithm (West's algorithm for weights).
+///
+/// # Example
+/// ```
+/// use multi_dim_point::stats::Accumulator;
+/// use multi_dim_point::Point;
+/// let mut acc = Accumulator::new(2);
+/// acc.push(&Point::new_from_vec(&vec![1.0, 10.0]));
+/// acc.push(&Point::new_from_vec(&vec![3.0, 30.0]));
+/// assert_eq!(acc.mean().unwrap().get_vector(), &vec![2.0, 20.0]);
+/// assert_eq!(acc.variance().unwrap().get_vector(), &vec![1.0, 100.0]);
+/// assert_eq!(acc.sample_covariance().unwrap(), vec![vec![2.0, 20.0], vec![20.0, 200.0]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Accumulator<T> {
+    dim: usize,
+    count: usize,
+    weight: T,
+    mean: Vec<T>,
+    /// The sums of the weighted products of the deviations from the mean (the diagonal is the
+    /// sum of squared deviations).
+    comoment: Vec<Vec<T>>,
+    min: Vec<T>,
+    max: Vec<T>,
+}
+
+impl<T> Accumulator<T>
+where
+    T: Float,
+{
+    /// Creates an empty accumulator of `dim`-dimensional points.
+    pub fn new(dim: usize) -> Accumulator<T> {
+        Accumulator {
+            dim,
+            count: 0,
+            weight: T::zero(),
+            mean: vec![T::zero(); dim],
+            comoment: vec![vec![T::zero(); dim]; dim],
+            min: vec![T::infinity(); dim],
+            max: vec![T::neg_infinity(); dim],
+        }
+    }
+
+    /// Add a point.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the accumulator are not
+    /// equal.
+    pub fn push(&mut self, point: &Point<T>) {
+        self.push_weighted(point, T::one());
+    }
+
+    /// Add a point with a weight.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the accumulator are not
+    /// equal, or if the weight is negative.
+    pub fn push_weighted(&mut self, point: &Point<T>, weight: T) {
+        if point.get_size() != self.dim {
+            panic!("dimensions are not equal");
+        }
+        if weight < T::zero() {
+            panic!("weights can't be negative");
+        }
+        let values = point.get_vector();
+        self.count += 1;
+        for (i, v) in values.iter().enumerate() {
+            self.min[i] = self.min[i].min(*v);
+            self.max[i] = self.max[i].max(*v);
+        }
+        if weight.is_zero() {
+            return;
+        }
+        self.weight = self.weight + weight;
+        let ratio = weight / self.weight;
+        let delta: Vec<T> = values
+            .iter()
+            .zip(&self.mean)
+            .map(|(v, m)| *v - *m)
+            .collect();
+        for (m, d) in self.mean.iter_mut().zip(&delta) {
+            *m = *m + *d * ratio;
+        }
+        for (i, row) in self.comoment.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = *c + weight * delta[i] * (values[j] - self.mean[j]);
+            }
+        }
+    }
+
+    /// The dimension of the points.
+    pub fn get_size(&self) -> usize {
+        self.dim
+    }
+
+    /// The number of points added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The sum of the weights of the points added.
+    pub fn total_weight(&self) -> T {
+        self.weight
+    }
+
+    /// The per-axis minimum.
+    pub fn min(&self) -> Option<Point<T>> {
+        self.non_empty(|| self.min.clone())
+    }
+
+    /// The per-axis maximum.
+    pub fn max(&self) -> Option<Point<T>> {
+        self.non_empty(|| self.max.clone())
+    }
+
+    /// The (weighted) mean. `None` if the total weight is 0.
+    pub fn mean(&self) -> Option<Point<T>> {
+        self.with_weight(T::zero(), || self.mean.clone())
+    }
+
+    /// The per-axis population variance (the mean squared deviation). `None` if the total weight
+    /// is 0.
+    pub fn variance(&self) -> Option<Point<T>> {
+        self.with_weight(T::zero(), || self.diagonal(self.weight))
+    }
+
+    /// The per-axis sample variance (with Bessel's correction, treating the weights as
+    /// frequencies). `None` if the total weight is not greater than 1.
+    pub fn sample_variance(&self) -> Option<Point<T>> {
+        self.with_weight(T::one(), || self.diagonal(self.weight - T::one()))
+    }
+
+    /// The population covariance matrix (a vector of rows). `None` if the total weight is 0.
+    pub fn covariance(&self) -> Option<Vec<Vec<T>>> {
+        self.scaled_comoment(self.weight, T::zero())
+    }
+
+    /// The sample covariance matrix (a vector of rows, with Bessel's correction). `None` if the
+    /// total weight is not greater than 1.
+    pub fn sample_covariance(&self) -> Option<Vec<Vec<T>>> {
+        self.scaled_comoment(self.weight - T::one(), T::one())
+    }
+
+    fn non_empty<F: FnOnce() -> Vec<T>>(&self, values: F) -> Option<Point<T>> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(Point::new_from_vec(&values()))
+        }
+    }
+
+    fn with_weight<F: FnOnce() -> Vec<T>>(&self, minimum: T, values: F) -> Option<Point<T>> {
+        if self.weight > minimum {
+            Some(Point::new_from_vec(&values()))
+        } else {
+            None
+        }
+    }
+
+    fn diagonal(&self, divisor: T) -> Vec<T> {
+        (0..self.dim)
+            .map(|i| self.comoment[i][i] / divisor)
+            .collect()
+    }
+
+    fn scaled_comoment(&self, divisor: T, minimum: T) -> Option<Vec<Vec<T>>> {
+        if self.weight <= minimum {
+            return None;
+        }
+        Some(
+            self.comoment
+                .iter()
+                .map(|row| row.iter().map(|c| *c / divisor).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<'a, T> Extend<&'a Point<T>> for Accumulator<T>
+where
+    T: Float + 'a,
+{
+    fn extend<I: IntoIterator<Item = &'a Point<T>>>(&mut self, points: I) {
+        for p in points {
+            self.push(p);
+        }
+    }
+}
+
+/// Accumulate the points, taking the dimension from the first point.
+fn accumulate<'a, T, I>(points: I) -> Option<Accumulator<T>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    let mut points = points.into_iter().peekable();
+    let mut acc = Accumulator::new(points.peek()?.get_size());
+    acc.extend(points);
+    Some(acc)
+}
+
+/// The centroid (mean) of the points.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::centroid;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![4.0, 0.0]),
+///     Point::new_from_vec(&vec![2.0, 3.0]),
+/// ];
+/// assert_eq!(centroid(&points).unwrap().get_vector(), &vec![2.0, 1.0]);
+/// ```
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn centroid<'a, T, I>(points: I) -> Option<Point<T>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    accumulate(points)?.mean()
+}
+
+/// The weighted centroid of the points. `None` if there are no points or the total weight is 0.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::weighted_centroid;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.0]),
+///     Point::new_from_vec(&vec![4.0]),
+/// ];
+/// assert_eq!(weighted_centroid(&points, &[3.0, 1.0]).unwrap().get_vector(), &vec![1.0]);
+/// ```
+/// # Panic
+/// This function will panic if the numbers of points and weights are not equal, if the
+/// dimensions of the points are not equal, or if a weight is negative.
+pub fn weighted_centroid<T: Float>(points: &[Point<T>], weights: &[T]) -> Option<Point<T>> {
+    if points.len() != weights.len() {
+        panic!("expected {} weights, found {}", points.len(), weights.len());
+    }
+    let mut acc = Accumulator::new(points.first()?.get_size());
+    for (p, w) in points.iter().zip(weights) {
+        acc.push_weighted(p, *w);
+    }
+    acc.mean()
+}
+
+/// The per-axis minimum of the points.
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn min<'a, T, I>(points: I) -> Option<Point<T>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    accumulate(points)?.min()
+}
+
+/// The per-axis maximum of the points.
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn max<'a, T, I>(points: I) -> Option<Point<T>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    accumulate(points)?.max()
+}
+
+/// The per-axis population variance of the points. See `Accumulator::sample_variance` for the
+/// sample variance.
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn variance<'a, T, I>(points: I) -> Option<Point<T>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    accumulate(points)?.variance()
+}
+
+/// The population covariance matrix of the points (a vector of rows). See
+/// `Accumulator::sample_covariance` for the sample covariance.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::covariance;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![1.0, -1.0]),
+///     Point::new_from_vec(&vec![-1.0, 1.0]),
+/// ];
+/// assert_eq!(covariance(&points).unwrap(), vec![vec![1.0, -1.0], vec![-1.0, 1.0]]);
+/// ```
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn covariance<'a, T, I>(points: I) -> Option<Vec<Vec<T>>>
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a Point<T>>,
+{
+    accumulate(points)?.covariance()
+}
+
+/// A total order of floats, with NaN greater than all the other values.
+fn total_cmp<T: Float>(a: &T, b: &T) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.partial_cmp(b).unwrap(),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// The sorted values of each axis.
+fn sorted_axes<T: Float>(points: &[Point<T>]) -> Option<Vec<Vec<T>>> {
+    let dim = points.first()?.get_size();
+    if points.iter().any(|p| p.get_size() != dim) {
+        panic!("dimensions are not equal");
+    }
+    Some(
+        (0..dim)
+            .map(|i| {
+                let mut axis: Vec<T> = points.iter().map(|p| p.get_vector()[i]).collect();
+                axis.sort_by(total_cmp);
+                axis
+            })
+            .collect(),
+    )
+}
+
+/// The `q` quantile of sorted values, interpolating linearly between the closest values.
+fn sorted_quantile<T: Float>(sorted: &[T], q: T) -> T {
+    let position = q * T::from(sorted.len() - 1).unwrap();
+    let below = position.floor();
+    let i = below.to_usize().unwrap();
+    if i + 1 >= sorted.len() {
+        return sorted[sorted.len() - 1];
+    }
+    sorted[i] + (sorted[i + 1] - sorted[i]) * (position - below)
+}
+
+/// The per-axis (componentwise) `q` quantile of the points, interpolating linearly between the
+/// closest values (like the default of R and NumPy). NaN values are sorted as the greatest.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::quantile;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = (0..5)
+///     .map(|i| Point::new_from_vec(&vec![i as f64, -10.0 * i as f64]))
+///     .collect();
+/// assert_eq!(quantile(&points, 0.25).unwrap().get_vector(), &vec![1.0, -30.0]);
+/// assert_eq!(quantile(&points, 0.875).unwrap().get_vector(), &vec![3.5, -5.0]);
+/// ```
+/// # Panic
+/// This function will panic if `q` is not in `[0, 1]`, or if the dimensions of the points are
+/// not equal.
+pub fn quantile<T: Float>(points: &[Point<T>], q: T) -> Option<Point<T>> {
+    quantiles(points, &[q]).map(|mut q| q.remove(0))
+}
+
+/// The per-axis quantiles of the points, for each `q` in `qs` (sorting the values once).
+/// # Panic
+/// This function will panic if a `q` is not in `[0, 1]`, or if the dimensions of the points are
+/// not equal.
+pub fn quantiles<T: Float>(points: &[Point<T>], qs: &[T]) -> Option<Vec<Point<T>>> {
+    if qs.iter().any(|q| !(*q >= T::zero() && *q <= T::one())) {
+        panic!("quantiles must be in [0, 1]");
+    }
+    let axes = sorted_axes(points)?;
+    Some(
+        qs.iter()
+            .map(|q| {
+                Point::new_from_vec(&axes.iter().map(|axis| sorted_quantile(axis, *q)).collect())
+            })
+            .collect(),
+    )
+}
+
+/// The per-axis (componentwise) median of the points.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::median;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![1.0, 7.0]),
+///     Point::new_from_vec(&vec![100.0, 5.0]),
+///     Point::new_from_vec(&vec![2.0, 6.0]),
+///     Point::new_from_vec(&vec![3.0, 8.0]),
+/// ];
+/// assert_eq!(median(&points).unwrap().get_vector(), &vec![2.5, 6.5]);
+/// ```
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn median<T: Float>(points: &[Point<T>]) -> Option<Point<T>> {
+    quantile(points, T::from(0.5).unwrap())
+}
+
+/// The per-axis trimmed mean of the points: the mean of each axis without the `proportion` of
+/// the lowest values and the `proportion` of the highest values.
+/// # Example
+/// ```
+/// use multi_dim_point::stats::trimmed_mean;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = [1.0, 2.0, 3.0, 4.0, 1000.0]
+///     .iter()
+///     .map(|v| Point::new_from_vec(&vec![*v]))
+///     .collect();
+/// assert_eq!(trimmed_mean(&points, 0.2).unwrap().get_vector(), &vec![3.0]);
+/// ```
+/// # Panic
+/// This function will panic if `proportion` is not in `[0, 0.5)`, or if the dimensions of the
+/// points are not equal.
+pub fn trimmed_mean<T: Float>(points: &[Point<T>], proportion: T) -> Option<Point<T>> {
+    if !(proportion >= T::zero() && proportion < T::from(0.5).unwrap()) {
+        panic!("the trimmed proportion must be in [0, 0.5)");
+    }
+    let axes = sorted_axes(points)?;
+    let trimmed = (proportion * T::from(points.len()).unwrap())
+        .floor()
+        .to_usize()
+        .unwrap();
+    let means = axes
+        .iter()
+        .map(|axis| {
+            let values = &axis[trimmed..points.len() - trimmed];
+            values.iter().fold(T::zero(), |sum, v| sum + *v) / T::from(values.len()).unwrap()
+        })
+        .collect();
+    Some(Point::new_from_vec(&means))
+}
+
+/// Options of the Weiszfeld algorithm for the geometric median.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeiszfeldOptions<T> {
+    tolerance: T,
+    max_iterations: usize,
+}
+
+impl<T> WeiszfeldOptions<T>
+where
+    T: Float,
+{
+    /// The default options: a tolerance of `1e-10` and up to 1000 iterations.
+    pub fn new() -> WeiszfeldOptions<T> {
+        WeiszfeldOptions {
+            tolerance: T::from(1e-10).unwrap(),
+            max_iterations: 1000,
+        }
+    }
+
+    /// Stop when an iteration moves the estimate less than the tolerance. The tolerance is a
+    /// distance, in the units of the points (not relative to them), so scale it with the data.
+    pub fn tolerance(mut self, tolerance: T) -> WeiszfeldOptions<T> {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Stop after this number of iterations.
+    pub fn max_iterations(mut self, max_iterations: usize) -> WeiszfeldOptions<T> {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+impl<T> Default for WeiszfeldOptions<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        WeiszfeldOptions::new()
+    }
+}
+
+/// The result of the Weiszfeld algorithm.
+#[derive(Debug, Clone)]
+pub struct GeometricMedian<T> {
+    /// The estimate of the geometric median.
+    pub point: Point<T>,
+    /// The number of iterations done.
+    pub iterations: usize,
+    /// `true` if the last iteration moved the estimate less than the tolerance.
+    pub converged: bool,
+}
+
+/// The geometric median of the points: the point minimizing the sum of the distances to them,
+/// which is robust to outliers.
+///
+/// Uses Weiszfeld's algorithm starting from the centroid, with the modification of Vardi and
+/// Zhang when an estimate lands on one of the points (where the plain algorithm is undefined).
+/// # Example
+/// ```
+/// use multi_dim_point::stats::{geometric_median, WeiszfeldOptions};
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.0, 0.0]),
+///     Point::new_from_vec(&vec![1.0, 0.0]),
+///     Point::new_from_vec(&vec![0.0, 1.0]),
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![100.0, 100.0]),
+/// ];
+/// let median = geometric_median(&points, &WeiszfeldOptions::new()).unwrap();
+/// assert!(median.converged);
+/// assert!(median.point.close(&Point::new_from_vec(&vec![0.5, 0.5]), 0.5));
+/// ```
+/// # Panic
+/// This function will panic if the dimensions of the points are not equal.
+pub fn geometric_median<T: Float>(
+    points: &[Point<T>],
+    options: &WeiszfeldOptions<T>,
+) -> Option<GeometricMedian<T>> {
+    let mut estimate = centroid(points)?.get_vector().clone();
+    let dim = estimate.len();
+    for iteration in 1..=options.max_iterations {
+        // the points closer than the rounding error of the estimate are on it.
+        let scale = estimate.iter().fold(T::zero(), |m, v| m.max(v.abs()));
+        let mut weight_sum = T::zero();
+        let mut weighted = vec![T::zero(); dim];
+        // the number of points the estimate is on, and the sum of the unit vectors towards the
+        // other points.
+        let mut coinciding = 0;
+        let mut pull = vec![T::zero(); dim];
+        for p in points {
+            let values = p.get_vector();
+            let distance = values
+                .iter()
+                .zip(&estimate)
+                .fold(T::zero(), |sum, (v, e)| sum + (*v - *e) * (*v - *e))
+                .sqrt();
+            if distance <= T::epsilon() * scale {
+                coinciding += 1;
+                continue;
+            }
+            let w = distance.recip();
+            weight_sum = weight_sum + w;
+            for i in 0..dim {
+                weighted[i] = weighted[i] + values[i] * w;
+                pull[i] = pull[i] + (values[i] - estimate[i]) * w;
+            }
+        }
+        if weight_sum.is_zero() {
+            // all the points are on the estimate.
+            return Some(GeometricMedian {
+                point: Point::new_from_vec(&estimate),
+                iterations: iteration,
+                converged: true,
+            });
+        }
+        let mut next: Vec<T> = weighted.iter().map(|v| *v / weight_sum).collect();
+        if coinciding > 0 {
+            let r = pull.iter().fold(T::zero(), |sum, v| sum + *v * *v).sqrt();
+            let gamma = (T::from(coinciding).unwrap() / r).min(T::one());
+            for (n, e) in next.iter_mut().zip(&estimate) {
+                *n = (T::one() - gamma) * *n + gamma * *e;
+            }
+        }
+        let step = next
+            .iter()
+            .zip(&estimate)
+            .fold(T::zero(), |sum, (n, e)| sum + (*n - *e) * (*n - *e))
+            .sqrt();
+        estimate = next;
+        if step <= options.tolerance {
+            return Some(GeometricMedian {
+                point: Point::new_from_vec(&estimate),
+                iterations: iteration,
+                converged: true,
+            });
+        }
+    }
+    Some(GeometricMedian {
+        point: Point::new_from_vec(&estimate),
+        iterations: options.max_iterations,
+        converged: false,
+    })
+}
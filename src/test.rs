@@ -153,6 +153,30 @@ mod tests {
         let s: i32 = -2;
         assert_eq!((p1 * s).get_vector(), &[-2, -4, -10, -18, 0])
     }
+    //scalar on the left and broadcasting tests
+    #[test]
+    fn mul_scalar_left() {
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, -2, 3]);
+        assert_eq!((&-2 * &p1).get_vector(), &[-2, 4, -6]);
+        assert_eq!((3 * p1).get_vector(), &[3, -6, 9])
+    }
+    #[test]
+    fn mul_scalar_left_float() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 0.5]);
+        assert_eq!((2.0 * p1).get_vector(), &[2.0, 1.0])
+    }
+    #[test]
+    fn add_scalar() {
+        let p1: Point<u8> = Point::new_from_vec(&vec![1, 2, 3]);
+        assert_eq!((&p1 + &2).get_vector(), &[3, 4, 5]);
+        assert_eq!((p1 + 0).get_vector(), &[1, 2, 3])
+    }
+    #[test]
+    fn sub_scalar() {
+        let p1: Point<f32> = Point::new_from_vec(&vec![1.5, 2.0, -3.0]);
+        assert_eq!((&p1 - &0.5).get_vector(), &[1.0, 1.5, -3.5]);
+        assert_eq!((p1 - 1.0).get_vector(), &[0.5, 1.0, -4.0])
+    }
     //div tests
     #[test]
     fn div() {
@@ -185,12 +209,272 @@ mod tests {
         let s: i32 = -2;
         assert_eq!((p1 / s).get_vector(), &[-1, -4, -32, -128, 0])
     }
+    //hadamard tests
+    #[test]
+    fn hadamard_mul() {
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, -2, 3, 0]);
+        let p2: Point<i32> = Point::new_from_vec(&vec![4, 5, -6, 9]);
+        assert_eq!(p1.hadamard_mul(&p2).get_vector(), &[4, -10, -18, 0])
+    }
+    #[test]
+    fn hadamard_div() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 9.0, -4.0]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![2.0, 3.0, 8.0]);
+        assert_eq!(p1.hadamard_div(&p2).get_vector(), &[0.5, 3.0, -0.5])
+    }
+    #[test]
+    #[should_panic]
+    fn hadamard_div_in_zero() {
+        let p1: Point<u32> = Point::new_from_vec(&vec![1, 2]);
+        let p2: Point<u32> = Point::new_from_vec(&vec![1, 0]);
+        let _ = p1.hadamard_div(&p2);
+    }
+    #[test]
+    fn rem() {
+        let p1: Point<u32> = Point::new_from_vec(&vec![7, 10, 20, 3]);
+        let p2: Point<u32> = Point::new_from_vec(&vec![4, 5, 6, 7]);
+        assert_eq!((&p1 % &p2).get_vector(), &[3, 0, 2, 3]);
+        assert_eq!((p1 % p2).get_vector(), &[3, 0, 2, 3])
+    }
+    #[test]
+    #[should_panic]
+    fn rem_not_identical_dim() {
+        let p1: Point<u32> = Point::new_from_vec(&vec![1, 2, 3]);
+        let p2: Point<u32> = Point::new_from_vec(&vec![1, 2]);
+        let _ = p1 % p2;
+    }
+    //text format tests
+    #[test]
+    fn display() {
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, -2, 3]);
+        assert_eq!(p1.to_string(), "(1, -2, 3)");
+        assert_eq!(Point::<i32>::new(0).to_string(), "()");
+    }
+    #[test]
+    fn display_precision() {
+        let p1: Point<f32> = Point::new_from_vec(&vec![1.0, 0.125]);
+        assert_eq!(format!("{:.1}", p1), "(1.0, 0.1)");
+    }
+    #[test]
+    fn debug() {
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, 2]);
+        assert!(format!("{:?}", p1).contains("[1, 2]"));
+    }
+    #[test]
+    fn parse_notations() {
+        for s in &["(1,2,3)", "[1 2 3]", "1;2;3", " { 1 , 2 , 3 } ", "1\t2\n3"] {
+            let p: Point<i32> = s.parse().unwrap();
+            assert_eq!(p.get_vector(), &[1, 2, 3]);
+        }
+    }
+    #[test]
+    fn parse_round_trip() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.5, -0.25, 1e10]);
+        let p2: Point<f64> = p1.to_string().parse().unwrap();
+        assert_eq!(p1, p2);
+        let p3: Point<u8> = Point::new(0);
+        assert_eq!(p3.to_string().parse::<Point<u8>>().unwrap(), p3);
+    }
+    #[test]
+    fn parse_errors() {
+        use crate::ParsePointError;
+        assert_eq!("  ".parse::<Point<i32>>(), Err(ParsePointError::Empty));
+        assert_eq!(
+            "(1, 2".parse::<Point<i32>>(),
+            Err(ParsePointError::UnbalancedBrackets)
+        );
+        assert_eq!(
+            "[1, 2)".parse::<Point<i32>>(),
+            Err(ParsePointError::UnbalancedBrackets)
+        );
+        match "1,,3".parse::<Point<i32>>() {
+            Err(ParsePointError::InvalidValue {
+                dimension, value, ..
+            }) => {
+                assert_eq!(dimension, 2);
+                assert_eq!(value, "");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+    //csv tests
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_read_all_columns() {
+        use crate::io::csv::{read_points, CsvOptions};
+        let data = "a,b,c\n1,2,3\n4, 5 ,6\n";
+        let points: Vec<Point<u32>> = read_points(data.as_bytes(), &CsvOptions::new()).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].get_vector(), &[4, 5, 6]);
+    }
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_payload_is_not_a_coordinate() {
+        use crate::io::csv::{read_points_with_payload, CsvOptions};
+        let data = "label,x,y\nfirst,1,2\nsecond,3,4\n";
+        let options = CsvOptions::new().payload_columns(&["label"]);
+        let points = read_points_with_payload::<i64, _>(data.as_bytes(), &options).unwrap();
+        assert_eq!(points[0].0.get_vector(), &[1, 2]);
+        assert_eq!(points[0].1, vec!["first".to_string()]);
+    }
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_errors() {
+        use crate::io::csv::{read_points, CsvError, CsvOptions};
+        let data = "x,y\n1,2\n3,4,5\n";
+        let err = read_points::<i32, _>(data.as_bytes(), &CsvOptions::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::InconsistentDimension {
+                line: 3,
+                expected: 2,
+                found: 3
+            }
+        ));
+        let data = "x,y\n1,2\n3,four\n";
+        let err = read_points::<i32, _>(data.as_bytes(), &CsvOptions::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::InvalidValue {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+        let options = CsvOptions::new().columns(&["z"]);
+        let err = read_points::<i32, _>(data.as_bytes(), &options).unwrap_err();
+        assert!(matches!(err, CsvError::UnknownColumn(_)));
+    }
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_round_trip() {
+        use crate::io::csv::{read_points, write_points, CsvOptions};
+        let points = vec![
+            Point::new_from_vec(&vec![1.5, -2.0]),
+            Point::new_from_vec(&vec![0.0, 1e-3]),
+        ];
+        let mut out = Vec::new();
+        write_points(&mut out, &points, None).unwrap();
+        let options = CsvOptions::new().has_headers(false);
+        let read: Vec<Point<f64>> = read_points(out.as_slice(), &options).unwrap();
+        assert!(read == points);
+    }
+    //binary format tests
+    #[test]
+    fn binary_round_trip() {
+        use crate::io::binary::{BinaryReader, BinaryWriter};
+        use std::io::Cursor;
+        let points = vec![
+            Point::new_from_vec(&vec![1u16, 2, 3]),
+            Point::new_from_vec(&vec![4, 5, 65535]),
+        ];
+        let mut writer = BinaryWriter::new(Cursor::new(Vec::new()), 3).unwrap();
+        for p in &points {
+            writer.write(p).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+        assert_eq!(bytes.len(), 24 + 2 * 3 * 2);
+        let reader = BinaryReader::<u16, _>::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.header().dimension, 3);
+        let read: Vec<Point<u16>> = reader.map(Result::unwrap).collect();
+        assert!(read == points);
+    }
+    #[test]
+    fn binary_errors() {
+        use crate::io::binary::{BinaryError, BinaryReader, BinaryWriter};
+        use std::io::Cursor;
+        let mut writer = BinaryWriter::<f32, _>::new(Cursor::new(Vec::new()), 2).unwrap();
+        assert!(matches!(
+            writer.write_slice(&[1.0]),
+            Err(BinaryError::DimensionMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+        writer.write_slice(&[1.0, 2.0]).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        assert!(matches!(
+            BinaryReader::<f64, _>::new(bytes.as_slice()),
+            Err(BinaryError::ScalarTypeMismatch { .. })
+        ));
+        assert!(matches!(
+            BinaryReader::<f32, _>::new(&b"not a point file, really"[..]),
+            Err(BinaryError::BadMagic)
+        ));
+        let mut truncated = BinaryReader::<f32, _>::new(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(matches!(truncated.next(), Some(Err(BinaryError::Io(_)))));
+        assert!(truncated.next().is_none());
+    }
+    #[test]
+    fn binary_crafted_header() {
+        use crate::io::binary::{BinaryError, BinaryReader, Header, ScalarType};
+        let header = |dimension: usize, count: u64| {
+            Header {
+                scalar_type: ScalarType::F64,
+                dimension,
+                count,
+            }
+            .to_bytes()
+        };
+        // the size of the data overflows
+        let bytes = header(1 << 31, 1 << 40);
+        assert!(matches!(
+            BinaryReader::<f64, _>::new(&bytes[..]),
+            Err(BinaryError::TooLarge)
+        ));
+        // huge but valid sizes don't allocate before the data is read
+        let bytes = header(u32::MAX as usize, 2);
+        let mut reader = BinaryReader::<f64, _>::new(&bytes[..]).unwrap();
+        assert!(matches!(reader.next(), Some(Err(BinaryError::Io(_)))));
+        // a dimension that doesn't fit in the header
+        if let Ok(dimension) = std::convert::TryFrom::try_from(1_u64 << 32) {
+            use crate::io::binary::BinaryWriter;
+            let writer = BinaryWriter::<f64, _>::new(std::io::Cursor::new(Vec::new()), dimension);
+            assert!(matches!(writer, Err(BinaryError::TooLarge)));
+        }
+    }
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn binary_mmap() {
+        use crate::io::binary::{BinaryError, BinaryWriter, MmapPoints};
+        use std::fs::File;
+        let path = std::env::temp_dir().join("multi_dim_point_mmap_test.mdpt");
+        let file = File::create(&path).unwrap();
+        let mut writer = BinaryWriter::<f64, _>::new(file, 2).unwrap();
+        for i in 0..100 {
+            writer.write_slice(&[i as f64, -i as f64]).unwrap();
+        }
+        writer.finish().unwrap();
+        let points = unsafe { MmapPoints::<f64>::open(&path) }.unwrap();
+        assert_eq!(points.len(), 100);
+        assert_eq!(points.get(42), Some(&[42.0, -42.0][..]));
+        assert_eq!(points.get(100), None);
+        assert_eq!(points.iter().count(), 100);
+        assert!(points.point(7).unwrap() == Point::new_from_vec(&vec![7.0, -7.0]));
+        assert!(matches!(
+            unsafe { MmapPoints::<u64>::open(&path) },
+            Err(BinaryError::ScalarTypeMismatch { .. })
+        ));
+        // a header whose size overflows
+        let header = crate::io::binary::Header {
+            scalar_type: crate::io::binary::ScalarType::F64,
+            dimension: 1 << 31,
+            count: 1 << 40,
+        };
+        std::fs::write(&path, header.to_bytes()).unwrap();
+        assert!(matches!(
+            unsafe { MmapPoints::<f64>::open(&path) },
+            Err(BinaryError::TooLarge)
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
     //aplly func test
+    type AddFn = Box<dyn Fn(&i32, &i32) -> i32>;
     #[test]
     fn apply_add_closure() {
         let p1: Point<i32> = Point::new_from_vec(&vec![2, 8, 64, 256, 0]);
         let p2: Point<i32> = Point::new_from_vec(&vec![12, 18, 164, 1256, 0]);
-        let add_f: Box<dyn Fn(&i32, &i32) -> i32> = Box::new(|a, b| a + b);
+        let add_f: AddFn = Box::new(|a, b| a + b);
         assert_eq!((p1.apply_func(&p2, &add_f)), &[14, 26, 228, 1512, 0])
     }
     fn apply_add_func() {
@@ -266,6 +550,1969 @@ mod tests {
         let p2: Point<i32> = Point::new_from_vec(&vec![2, 42]);
         assert_eq!(p1.close(&p2, 3), false);
     }
+    // approximate equality tests
+    #[test]
+    fn close_abs_diff_unsigned() {
+        let p1: Point<u8> = Point::new_from_vec(&vec![0, 255]);
+        let p2: Point<u8> = Point::new_from_vec(&vec![3, 250]);
+        assert!(p1.close_abs_diff(&p2, 5));
+        assert!(!p1.close_abs_diff(&p2, 4));
+        assert!(!p1.close_abs_diff(&Point::new(3), 255));
+    }
+    #[test]
+    fn close_relative() {
+        let p1: Point<f32> = Point::new_from_vec(&vec![1000.0, 0.0]);
+        let p2: Point<f32> = Point::new_from_vec(&vec![1001.0, 1e-7]);
+        assert!(p1.close_relative(&p2, 1e-6, 1e-3));
+        assert!(!p1.close_relative(&p2, 1e-6, 1e-4));
+        assert!(!p1.close_relative(&p2, 1e-8, 1e-3));
+    }
+    #[test]
+    fn close_ulps() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, -0.0, f64::INFINITY]);
+        let next = f64::from_bits(1.0f64.to_bits() + 2);
+        let p2: Point<f64> = Point::new_from_vec(&vec![next, 0.0, f64::INFINITY]);
+        assert!(p1.close_ulps(&p2, 0.0, 2));
+        assert!(!p1.close_ulps(&p2, 0.0, 1));
+        let nan: Point<f64> = Point::new_from_vec(&vec![f64::NAN]);
+        assert!(!nan.close_ulps(&nan, 1.0, u64::MAX));
+    }
+    #[test]
+    fn ulps_distance_across_zero() {
+        use crate::Ulps;
+        let tiny = f32::from_bits(1);
+        assert_eq!(tiny.ulps_distance(-tiny), Some(2));
+        assert_eq!(0.0f32.ulps_distance(-0.0), Some(0));
+        assert_eq!(
+            f64::MAX.ulps_distance(f64::MIN),
+            Some(0xffdf_ffff_ffff_fffe)
+        );
+    }
+    #[cfg(feature = "approx")]
+    #[test]
+    fn approx_traits() {
+        use approx::{assert_abs_diff_eq, assert_relative_eq, assert_ulps_eq};
+        let p1: Point<f64> = Point::new_from_vec(&vec![0.1 + 0.2, 1e10]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![0.3, 1e10 + 1e-5]);
+        assert_abs_diff_eq!(p1, p2, epsilon = 1.0);
+        assert_relative_eq!(p1, p2, max_relative = 1e-9);
+        assert_ulps_eq!(p1, p2, epsilon = 1e-15, max_ulps = 8);
+        approx::assert_abs_diff_ne!(p1, Point::new(3));
+    }
+    // hash and order tests
+    #[test]
+    fn hash_set_of_points() {
+        use std::collections::HashSet;
+        let mut set: HashSet<Point<i64>> = HashSet::new();
+        set.insert(Point::new_from_vec(&vec![1, 2]));
+        set.insert(Point::new_from_vec(&vec![1, 2]));
+        set.insert(Point::new_from_vec(&vec![2, 1]));
+        set.insert(Point::new_from_vec(&vec![1, 2, 0]));
+        assert_eq!(set.len(), 3);
+    }
+    #[test]
+    fn lexicographic_order() {
+        use std::collections::BTreeSet;
+        let set: BTreeSet<Point<i32>> = [vec![2, 0], vec![1, 5], vec![1], vec![1, -1]]
+            .iter()
+            .map(Point::new_from_vec)
+            .collect();
+        let sorted: Vec<&Vec<i32>> = set.iter().map(Point::get_vector).collect();
+        assert_eq!(
+            sorted,
+            vec![&vec![1], &vec![1, -1], &vec![1, 5], &vec![2, 0]]
+        );
+    }
+    #[test]
+    fn float_partial_order() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, f64::NAN]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![1.0, 2.0]);
+        assert_eq!(p1.partial_cmp(&p2), None);
+        assert!(Point::new_from_vec(&vec![0.5]) < p1);
+    }
+    #[test]
+    fn ordered_point() {
+        use crate::OrderedPoint;
+        use std::collections::HashMap;
+        let key = |v: Vec<f64>| OrderedPoint::new(Point::new_from_vec(&v));
+        assert!(key(vec![1.0, f64::INFINITY]) < key(vec![1.0, f64::NAN]));
+        assert!(key(vec![1.0, f64::NAN]) == key(vec![1.0, -f64::NAN]));
+        assert!(key(vec![-0.0]) == key(vec![0.0]));
+        assert!(key(vec![-1.0]) < key(vec![-0.0]));
+        assert!(key(vec![1.0]) < key(vec![1.0, 0.0]));
+        let mut map = HashMap::new();
+        map.insert(key(vec![0.0, f64::NAN]), 1);
+        map.insert(key(vec![-0.0, f64::NAN]), 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&key(vec![0.0, f64::NAN])], 2);
+    }
+    // view tests
+    #[test]
+    fn view_read_only() {
+        use crate::PointView;
+        let buffer = [1.0, 2.0, 3.0, 1.5, 2.5, 3.5];
+        let p1 = PointView::new(&buffer[..3]);
+        let p2 = PointView::new(&buffer[3..]);
+        assert_eq!(p1.get_size(), 3);
+        assert_eq!(p2.get_value(3), &3.5);
+        assert!(p1.close(&p2, 0.5));
+        assert!(!p1.close_relative(&p2, 0.1, 0.1));
+        assert_eq!((&p2 - &p1).get_vector(), &[0.5, 0.5, 0.5]);
+        assert_eq!((&p1 * &2.0).get_vector(), &[2.0, 4.0, 6.0]);
+        assert_eq!(p1.hadamard_mul(&p2).get_vector(), &[1.5, 5.0, 10.5]);
+        assert_eq!(p1.to_string(), "(1, 2, 3)");
+    }
+    #[test]
+    fn view_measures_and_operators() {
+        use crate::{PointView, PointViewMut};
+        use std::ops::Mul;
+        let buffer = [1.0, 2.0, 2.0, 0.0, -1.0, 1.0];
+        let (p1, p2) = (PointView::new(&buffer[..3]), PointView::new(&buffer[3..]));
+        let (q1, q2) = (p1.to_point(), p2.to_point());
+        assert_eq!(p1.dot(&p2), q1.dot(&q2));
+        assert_eq!(p1.norm(), 3.0);
+        assert_eq!(p1.distance(&p2), q1.distance(&q2));
+        let ints: [i32; 6] = [7, 10, 20, 4, 5, 6];
+        let (a, b) = (PointView::new(&ints[..3]), PointView::new(&ints[3..]));
+        assert_eq!((&a % &b).get_vector(), &[3, 0, 2]);
+        assert_eq!(a.hadamard_div(&b).get_vector(), &[1, 2, 3]);
+        assert_eq!((&a + &1).get_vector(), &[8, 11, 21]);
+        assert_eq!((&a - &1).get_vector(), &[6, 9, 19]);
+        let two = 2;
+        assert_eq!((2 * a).get_vector(), &[14, 20, 40]);
+        assert!(2 * a == Mul::mul(&two, &a));
+        let mut values = [1u8, 2];
+        let mut m = PointViewMut::new(&mut values);
+        m += 3;
+        m -= 1;
+        assert_eq!(values, [3, 4]);
+    }
+    #[test]
+    #[should_panic]
+    fn view_distance_not_identical_dim() {
+        use crate::PointView;
+        PointView::new(&[1.0, 2.0]).distance(&PointView::new(&[1.0]));
+    }
+    #[test]
+    fn view_and_point_equal() {
+        let p1: Point<u8> = Point::new_from_vec(&vec![1, 2]);
+        let v = p1.as_view();
+        assert!(v == p1);
+        assert!(p1 == v);
+        let p2: Point<u8> = v.into();
+        assert!(p1 == p2);
+    }
+    #[test]
+    #[should_panic]
+    fn view_add_not_identical_dim() {
+        use crate::PointView;
+        let _ = &PointView::new(&[1, 2]) + &PointView::new(&[1]);
+    }
+    #[test]
+    fn view_mut_in_place() {
+        use crate::PointViewMut;
+        let mut buffer = vec![1, 2, 3, 4, 5, 6];
+        {
+            let (first, second) = buffer.split_at_mut(3);
+            let mut p1 = PointViewMut::new(first);
+            let p2 = PointViewMut::new(second);
+            p1 += p2.as_view();
+            p1 -= Point::new_from_vec(&vec![1, 1, 1]).as_view();
+            p1 /= 2;
+            p1.set_value(1, &0);
+        }
+        assert_eq!(buffer, vec![0, 3, 4, 4, 5, 6]);
+        let mut p: Point<i32> = Point::new_from_vec(&vec![1, 2]);
+        p.as_view_mut().set_value(2, &7);
+        assert_eq!(p.get_vector(), &[1, 7]);
+    }
+    #[test]
+    #[should_panic]
+    fn view_mut_wrong_dim() {
+        use crate::PointViewMut;
+        let mut buffer = [1, 2];
+        PointViewMut::new(&mut buffer).set_value(0, &1);
+    }
+    // dot, norm and distance tests
+    #[test]
+    fn dot_norm_distance() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 2.0, 2.0]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![0.0, -1.0, 1.0]);
+        assert_eq!(p1.dot(&p2), 0.0);
+        assert_eq!(p1.norm(), 3.0);
+        assert_eq!(p1.distance(&p1), 0.0);
+        assert!((p1.distance(&p2) - 11f64.sqrt()).abs() < 1e-12);
+    }
+    #[test]
+    #[should_panic]
+    fn dot_not_identical_dim() {
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, 2]);
+        let p2: Point<i32> = Point::new_from_vec(&vec![1, 2, 3]);
+        let _ = p1.dot(&p2);
+    }
+    // interpolation tests
+    #[test]
+    fn lerp_ends_and_extrapolation() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, -1.0, 0.0]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![3.0, 1.0, 10.0]);
+        assert!(p1.lerp(&p2, 0.0) == p1);
+        assert!(p1.lerp(&p2, 1.0) == p2);
+        assert_eq!(p1.lerp(&p2, 2.0).get_vector(), &[5.0, 3.0, 20.0]);
+    }
+    #[test]
+    fn slerp_constant_angle_and_length() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![2.0, 0.0, 0.0]);
+        let p2: Point<f64> = Point::new_from_vec(&vec![0.0, 0.0, 4.0]);
+        let q = p1.slerp(&p2, 1.0 / 3.0);
+        let angle = std::f64::consts::FRAC_PI_6;
+        let expected = Point::new_from_vec(&vec![angle.cos(), 0.0, angle.sin()]) * (8.0 / 3.0);
+        assert!(q.close(&expected, 1e-12));
+        assert!(p1.slerp(&p2, 1.0).close(&p2, 1e-12));
+    }
+    #[test]
+    fn slerp_degenerate() {
+        let p1: Point<f64> = Point::new_from_vec(&vec![1.0, 0.0]);
+        let opposite: Point<f64> = Point::new_from_vec(&vec![-1.0, 0.0]);
+        let mid = p1.slerp(&opposite, 0.5);
+        assert!((mid.norm() - 1.0).abs() < 1e-12);
+        assert!(mid.dot(&p1).abs() < 1e-12);
+        let zero: Point<f64> = Point::new(2);
+        assert!(p1
+            .slerp(&zero, 0.5)
+            .close(&Point::new_from_vec(&vec![0.5, 0.0]), 1e-12));
+        assert!(p1.slerp(&p1, 0.3).close(&p1, 1e-12));
+        // NaN values give NaN values, without panicking.
+        let nan: Point<f64> = Point::new_from_vec(&vec![f64::NAN, 0.0]);
+        assert!(p1.slerp(&nan, 0.5).x().is_nan());
+        assert!(nan.slerp(&opposite, 0.5).x().is_nan());
+        assert!(p1.slerp(&opposite, f64::NAN).x().is_nan());
+    }
+    #[test]
+    fn bezier_cubic() {
+        use crate::interpolate::Bezier;
+        let points: Vec<Point<f64>> = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]]
+            .iter()
+            .map(|v| Point::new_from_vec(&v.to_vec()))
+            .collect();
+        let curve = Bezier::new(points.clone());
+        assert!(curve.eval(0.0) == points[0]);
+        assert!(curve.eval(1.0) == points[3]);
+        assert!(curve
+            .eval(0.5)
+            .close(&Point::new_from_vec(&vec![0.5, 0.75]), 1e-12));
+        // B'(0) = 3 (P1 - P0)
+        assert!(curve
+            .tangent(0.0)
+            .close(&Point::new_from_vec(&vec![0.0, 3.0]), 1e-12));
+        assert_eq!(curve.derivative().derivative().derivative().degree(), 0);
+        let (left, right) = curve.subdivide(0.3);
+        assert_eq!(left.degree(), 3);
+        assert!(left.eval(1.0).close(&curve.eval(0.3), 1e-12));
+        assert!(right.eval(0.0).close(&curve.eval(0.3), 1e-12));
+        assert!(right.eval(0.5).close(&curve.eval(0.65), 1e-12));
+    }
+    #[test]
+    #[should_panic]
+    fn bezier_not_identical_dim() {
+        use crate::interpolate::Bezier;
+        let _ = Bezier::new(vec![Point::<f64>::new(2), Point::new(3)]);
+    }
+    // spline tests
+    fn waypoints() -> Vec<Point<f64>> {
+        [
+            [0.0, 0.0, 0.0],
+            [1.0, 2.0, 0.0],
+            [3.0, 3.0, 1.0],
+            [4.0, 0.0, 2.0],
+        ]
+        .iter()
+        .map(|v| Point::new_from_vec(&v.to_vec()))
+        .collect()
+    }
+    /// Compare the tangent of the curve to a central difference.
+    fn check_tangent<C: crate::spline::Curve<f64>>(curve: &C, t: f64) {
+        let h = 1e-6;
+        let numeric = (&curve.eval(t + h) - &curve.eval(t - h)) / (2.0 * h);
+        assert!(curve.tangent(t).close(&numeric, 1e-5));
+    }
+    #[test]
+    fn catmull_rom_interpolates() {
+        use crate::spline::{CatmullRom, Curve};
+        let points = waypoints();
+        let curve = CatmullRom::new(points.clone());
+        for (i, p) in points.iter().enumerate() {
+            assert!(curve.eval(i as f64).close(p, 1e-12));
+        }
+        // the tangent in an inner point is half the difference of its neighbours.
+        let expected = (&points[2] - &points[0]) / 2.0;
+        assert!(curve.tangent(1.0).close(&expected, 1e-12));
+        for t in &[0.3, 1.5, 2.9] {
+            check_tangent(&curve, *t);
+        }
+    }
+    #[test]
+    fn b_spline_clamped_and_smooth() {
+        use crate::spline::{BSpline, Curve};
+        let points = waypoints();
+        let curve = BSpline::new(points.clone());
+        let (start, end) = curve.domain();
+        assert!(curve.eval(start).close(&points[0], 1e-12));
+        assert!(curve.eval(end).close(&points[3], 1e-12));
+        for t in &[0.5, 1.0, 2.5, 4.2] {
+            check_tangent(&curve, *t);
+        }
+        // continuous at the knots.
+        let h = 1e-9;
+        assert!(curve.eval(2.0 - h).close(&curve.eval(2.0 + h), 1e-6));
+    }
+    #[test]
+    fn natural_spline() {
+        use crate::spline::{Curve, NaturalCubicSpline};
+        let points = waypoints();
+        let curve = NaturalCubicSpline::new(points.clone());
+        for (i, p) in points.iter().enumerate() {
+            assert!(curve.eval(i as f64).close(p, 1e-12));
+        }
+        for t in &[0.2, 1.0, 1.7, 2.5] {
+            check_tangent(&curve, *t);
+        }
+        // zero second derivative at the ends.
+        let h = 1e-4;
+        let second = &(&curve.eval(2.0 * h) - &(&curve.eval(h) * &2.0)) + &curve.eval(0.0);
+        assert!(second.close(&Point::new(3), 1e-6));
+        // two points make a line.
+        let line = NaturalCubicSpline::new(points[..2].to_vec());
+        assert!(line
+            .eval(0.5)
+            .close(&points[0].lerp(&points[1], 0.5), 1e-12));
+    }
+    #[test]
+    fn arc_length_of_a_circle_quarter() {
+        use crate::spline::{ArcLength, Curve};
+        // a Catmull-Rom through many points of a circle is almost the circle.
+        let points: Vec<Point<f64>> = (0..=64)
+            .map(|i| {
+                let a = std::f64::consts::FRAC_PI_2 * i as f64 / 64.0;
+                Point::new_from_vec(&vec![a.cos(), a.sin()])
+            })
+            .collect();
+        let curve = crate::spline::CatmullRom::new(points);
+        assert!((curve.arc_length() - std::f64::consts::FRAC_PI_2).abs() < 1e-5);
+        let table = ArcLength::new(&curve);
+        let t = table.param_at(std::f64::consts::FRAC_PI_4);
+        assert!((t - 32.0).abs() < 1e-3);
+    }
+    #[test]
+    fn arc_length_nan_lengths() {
+        use crate::interpolate::Bezier;
+        use crate::spline::ArcLength;
+        let curve = Bezier::new(vec![
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![f64::NAN, 1.0]),
+            Point::new_from_vec(&vec![2.0, 0.0]),
+        ]);
+        let table = ArcLength::new(&curve);
+        assert!(table.total().is_nan());
+        assert_eq!(table.param_at(0.5), 0.0);
+        assert_eq!(table.param_at(f64::NAN), 0.0);
+        table.point_at(0.5);
+    }
+    #[test]
+    fn resample_by_distance() {
+        use crate::interpolate::Bezier;
+        use crate::spline::Curve;
+        let line = Bezier::new(vec![
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![0.5, 0.0]),
+            Point::new_from_vec(&vec![10.0, 0.0]),
+        ]);
+        let points = line.resample_by_distance(3.0);
+        let xs: Vec<f64> = points.iter().map(|p| *p.get_value(1)).collect();
+        assert_eq!(xs.len(), 5);
+        for (x, expected) in xs.iter().zip([0.0, 3.0, 6.0, 9.0, 10.0].iter()) {
+            assert!((x - expected).abs() < 1e-6);
+        }
+        let even = line.resample(11);
+        for (i, p) in even.iter().enumerate() {
+            assert!((p.get_value(1) - i as f64).abs() < 1e-6);
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn spline_needs_two_points() {
+        let _ = crate::spline::CatmullRom::new(vec![Point::<f64>::new(2)]);
+    }
+    // polyline tests
+    fn zigzag() -> crate::polyline::Polyline<f64> {
+        let points = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.05, 0.0],
+            [2.0, 0.0, 0.05],
+            [3.0, 3.0, 0.0],
+            [4.0, 6.0, 0.0],
+        ]
+        .iter()
+        .map(|v| Point::new_from_vec(&v.to_vec()))
+        .collect();
+        crate::polyline::Polyline::new(points)
+    }
+    #[test]
+    fn polyline_lengths() {
+        let line = zigzag();
+        let lengths = line.cumulative_lengths();
+        assert_eq!(lengths.len(), 5);
+        assert!((line.length() - lengths[4]).abs() < 1e-12);
+        assert!((lengths[4] - lengths[3] - 10f64.sqrt()).abs() < 1e-12);
+        assert!(line.point_at_distance(-1.0) == line.points()[0]);
+        assert!(line.point_at_distance(1e9) == line.points()[4]);
+        let middle = line.point_at_distance(lengths[3] + 10f64.sqrt() / 2.0);
+        assert!(middle.close(&Point::new_from_vec(&vec![3.5, 4.5, 0.0]), 1e-12));
+    }
+    #[test]
+    fn polyline_nan_distance() {
+        let line = zigzag();
+        let nan = line.point_at_distance(f64::NAN);
+        assert_eq!(nan.get_size(), 3);
+        assert!(nan.get_vector().iter().all(|v| v.is_nan()));
+        let mut points = line.points().to_vec();
+        points[2] = Point::new_from_vec(&vec![f64::NAN, 0.0, 0.0]);
+        let broken = crate::polyline::Polyline::new(points);
+        assert!(broken.point_at_distance(1.0).get_vector()[0].is_nan());
+        assert_eq!(broken.resample_count(3).len(), 3);
+    }
+    #[test]
+    fn polyline_resample() {
+        let line = zigzag();
+        let resampled = line.resample_count(7);
+        assert_eq!(resampled.len(), 7);
+        assert!(resampled.points()[6] == line.points()[4]);
+        // the new points are equally spaced along the original polyline.
+        let step = line.length() / 6.0;
+        for (i, p) in resampled.points().iter().enumerate() {
+            let nearest = line.nearest_point(p);
+            assert!(nearest.distance < 1e-9);
+            assert!((nearest.along - step * i as f64).abs() < 1e-9);
+        }
+        let by_step = line.resample(2.0);
+        assert_eq!(by_step.len(), (line.length() / 2.0).ceil() as usize + 1);
+        assert!(by_step.points()[1].close(&line.point_at_distance(2.0), 1e-12));
+    }
+    #[test]
+    fn polyline_nearest_point() {
+        let line = zigzag();
+        let nearest = line.nearest_point(&Point::new_from_vec(&vec![-3.0, -4.0, 0.0]));
+        assert!(nearest.point == line.points()[0]);
+        assert_eq!(nearest.segment, 0);
+        assert_eq!(nearest.along, 0.0);
+        assert_eq!(nearest.distance, 5.0);
+        let on_line = line.point_at_distance(4.0);
+        let nearest = line.nearest_point(&on_line);
+        assert!(nearest.distance < 1e-12);
+        assert!((nearest.along - 4.0).abs() < 1e-12);
+    }
+    #[test]
+    fn polyline_simplify() {
+        let line = zigzag();
+        let rdp = line.simplify_rdp(0.1);
+        let kept: Vec<f64> = rdp.points().iter().map(|p| *p.get_value(1)).collect();
+        assert_eq!(kept, vec![0.0, 2.0, 4.0]);
+        assert_eq!(line.simplify_rdp(0.0).len(), 5);
+        let vw = line.simplify_visvalingam(0.5);
+        let kept: Vec<f64> = vw.points().iter().map(|p| *p.get_value(1)).collect();
+        assert_eq!(kept, vec![0.0, 2.0, 4.0]);
+        assert_eq!(line.simplify_visvalingam(1e9).len(), 2);
+        assert_eq!(line.simplify_visvalingam(0.0).len(), 5);
+    }
+    #[test]
+    #[should_panic]
+    fn polyline_not_identical_dim() {
+        let _ = crate::polyline::Polyline::new(vec![Point::<f64>::new(2), Point::new(3)]);
+    }
+    // coordinates conversion tests
+    fn sample_points(dim: usize) -> Vec<Point<f64>> {
+        let values = [0.3, -1.7, 2.2, 0.0, -0.4, 5.0, 1.1, -3.3];
+        (0..values.len())
+            .map(|i| {
+                Point::new_from_vec(
+                    &(0..dim)
+                        .map(|d| values[(i + 3 * d) % values.len()])
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+    #[test]
+    fn polar_round_trip() {
+        for p in sample_points(2) {
+            let polar = p.to_polar();
+            assert!(*polar.get_value(1) >= 0.0);
+            assert!(Point::from_polar(&polar).close(&p, 1e-12));
+        }
+    }
+    #[test]
+    fn spherical_and_cylindrical_round_trip() {
+        for p in sample_points(3) {
+            let spherical = p.to_spherical();
+            let theta = *spherical.get_value(2);
+            assert!((0.0..=std::f64::consts::PI).contains(&theta));
+            assert!(Point::from_spherical(&spherical).close(&p, 1e-12));
+            assert!(Point::from_cylindrical(&p.to_cylindrical()).close(&p, 1e-12));
+        }
+    }
+    #[test]
+    fn hyperspherical_round_trip() {
+        for dim in 2..7 {
+            for p in sample_points(dim) {
+                let h = p.to_hyperspherical();
+                assert!((h.get_value(1) - p.norm()).abs() < 1e-12);
+                assert!(Point::from_hyperspherical(&h).close(&p, 1e-12));
+            }
+        }
+        // in 2D the hyperspherical coordinates are the polar coordinates.
+        let p: Point<f64> = Point::new_from_vec(&vec![-1.0, -1.0]);
+        assert!(p.to_hyperspherical().close(&p.to_polar(), 1e-15));
+        // the origin and points on an axis.
+        let zero: Point<f64> = Point::new(4);
+        assert!(Point::from_hyperspherical(&zero.to_hyperspherical()).close(&zero, 0.0));
+        let axis: Point<f64> = Point::new_from_vec(&vec![0.0, 0.0, -2.0, 0.0]);
+        assert!(Point::from_hyperspherical(&axis.to_hyperspherical()).close(&axis, 1e-12));
+    }
+    #[test]
+    #[should_panic]
+    fn polar_wrong_dim() {
+        let p: Point<f64> = Point::new(3);
+        let _ = p.to_polar();
+    }
+    // geographic tests
+    fn dms(d: f64, m: f64, s: f64) -> f64 {
+        d.signum() * (d.abs() + m / 60.0 + s / 3600.0)
+    }
+    #[test]
+    fn vincenty_flinders_peak_to_buninyong() {
+        use crate::geo;
+        let a = Point::new_from_vec(&vec![dms(-37.0, 57.0, 3.7203), dms(144.0, 25.0, 29.5244)]);
+        let b = Point::new_from_vec(&vec![dms(-37.0, 39.0, 10.1561), dms(143.0, 55.0, 35.3839)]);
+        let distance = geo::vincenty_distance(&a, &b).unwrap();
+        assert!((distance - 54_972.271).abs() < 0.001);
+        // the sphere is close, but not as accurate.
+        assert!((geo::haversine_distance(&a, &b) - distance).abs() < 0.005 * distance);
+        assert_eq!(geo::vincenty_distance(&a, &a), Some(0.0));
+    }
+    #[test]
+    fn vincenty_antipodal() {
+        use crate::geo;
+        let a = Point::new_from_vec(&vec![0.0, 0.0]);
+        let b = Point::new_from_vec(&vec![0.5, 179.7]);
+        assert_eq!(geo::vincenty_distance(&a, &b), None);
+    }
+    #[test]
+    fn bearings() {
+        use crate::geo;
+        let a = Point::new_from_vec(&vec![0.0, 0.0]);
+        let north = Point::new_from_vec(&vec![10.0, 0.0]);
+        assert!(geo::initial_bearing(&a, &north).abs() < 1e-12);
+        assert!((geo::initial_bearing(&north, &a) - 180.0).abs() < 1e-12);
+        // along a great circle that isn't a meridian or the equator, the bearing changes.
+        let b = Point::new_from_vec(&vec![40.0, 60.0]);
+        let initial = geo::initial_bearing(&a, &b);
+        let last = geo::final_bearing(&a, &b);
+        assert!(last > initial);
+        // the bearing from the midpoint is between them.
+        let mid = geo::midpoint(&a, &b);
+        let middle = geo::initial_bearing(&mid, &b);
+        assert!(initial < middle && middle < last);
+        let end = geo::destination(&mid, middle, geo::haversine_distance(&mid, &b));
+        assert!(end.close(&b, 1e-9));
+    }
+    #[test]
+    fn destination_crosses_the_antimeridian() {
+        use crate::geo;
+        let start = Point::new_from_vec(&vec![0.0, 179.5]);
+        let end = geo::destination(&start, 90.0, 111_195.0);
+        assert!(end.close(&Point::new_from_vec(&vec![0.0, -179.5]), 1e-3));
+    }
+    #[test]
+    fn ecef_round_trip() {
+        use crate::geo;
+        for lat in &[-90.0, -45.5, 0.0, 12.3, 89.9, 90.0] {
+            for lon in &[-180.0, -33.0, 0.0, 151.2] {
+                for alt in &[-100.0, 0.0, 8848.0, 400_000.0] {
+                    let p = Point::new_from_vec(&vec![*lat, *lon, *alt]);
+                    let back = geo::from_ecef(&geo::to_ecef(&p));
+                    assert!((back.get_value(1) - lat).abs() < 1e-9);
+                    assert!((back.get_value(3) - alt).abs() < 1e-6);
+                    if lat.abs() < 90.0 {
+                        assert!((back.get_value(2) - lon).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn geo_wrong_dim() {
+        let _ = crate::geo::haversine_distance(&Point::new(4), &Point::new(2));
+    }
+    // schema tests
+    #[test]
+    fn schema_access_by_name() {
+        use crate::Schema;
+        let schema = Schema::new(&["price", "volume", "spread"]);
+        assert_eq!(schema.dimension_of("spread"), Some(3));
+        let mut p: Point<f64> = Point::new_from_vec(&vec![1.0, 2.0, 3.0]).with_schema(&schema);
+        assert_eq!(p.get_by_name("volume"), Some(&2.0));
+        assert_eq!(p.get_by_name("missing"), None);
+        p.set_by_name("price", &4.0);
+        assert_eq!(p.x(), &4.0);
+        assert_eq!(Point::<f64>::new(3).get_by_name("price"), None);
+    }
+    #[test]
+    fn schema_is_kept() {
+        use crate::Schema;
+        use std::sync::Arc;
+        let schema = Schema::new(&["a", "b"]);
+        let p1: Point<i32> = Point::new_from_vec(&vec![1, 2]).with_schema(&schema);
+        let p2: Point<i32> = Point::new_from_vec(&vec![3, 4]);
+        assert!(Arc::ptr_eq((&p2 + &p1).schema().unwrap(), &schema));
+        assert!((&p1 - &p2).schema().is_some());
+        assert!((&p1 * &2).schema().is_some());
+        assert!((&p1 + &2).schema().is_some());
+        assert!(p1.hadamard_mul(&p2).schema().is_some());
+        assert!(p1.clone().schema().is_some());
+        assert!(p1.clone().without_schema().schema().is_none());
+        // equal schemas that are not shared can be combined too.
+        let p3: Point<i32> =
+            Point::new_from_vec(&vec![3, 4]).with_schema(&Schema::new(&["a", "b"]));
+        assert_eq!((&p1 + &p3).get_vector(), &[4, 6]);
+        // the schema is not part of the equality.
+        assert!(p1 == Point::new_from_vec(&vec![1, 2]));
+    }
+    #[test]
+    #[should_panic]
+    fn schema_mismatch_apply_func() {
+        use crate::Schema;
+        let p1: Point<i32> =
+            Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["a", "b"]));
+        let p2: Point<i32> =
+            Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["a", "c"]));
+        let _ = p1.apply_func(&p2, &|a, b| a + b);
+    }
+    #[test]
+    #[should_panic]
+    fn schema_mismatch_sub() {
+        use crate::Schema;
+        let p1: Point<i32> =
+            Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["a", "b"]));
+        let p2: Point<i32> =
+            Point::new_from_vec(&vec![1, 2]).with_schema(&Schema::new(&["x", "y"]));
+        let _ = p1 - p2;
+    }
+    #[test]
+    #[should_panic]
+    fn schema_wrong_size() {
+        let _ = Point::<i32>::new(3).with_schema(&crate::Schema::new(&["a", "b"]));
+    }
+    #[test]
+    #[should_panic]
+    fn named_accessor_missing_dim() {
+        let p: Point<i32> = Point::new_from_vec(&vec![1, 2]);
+        let _ = p.z();
+    }
+    // projective tests
+    #[test]
+    fn homogeneous_round_trip() {
+        let p: Point<f64> = Point::new_from_vec(&vec![1.5, -2.0, 3.0]);
+        let h = p.to_homogeneous();
+        assert_eq!(h.get_vector(), &vec![1.5, -2.0, 3.0, 1.0]);
+        assert_eq!(h.from_homogeneous().unwrap(), p);
+        assert_eq!((&h * &4.0).from_homogeneous().unwrap(), p);
+        assert!(Point::new_from_vec(&vec![1.0, 2.0, 0.0])
+            .from_homogeneous()
+            .is_none());
+    }
+    #[test]
+    fn projective_inverse_is_relative_to_scale() {
+        use crate::projective::ProjectiveTransform;
+        // small but well-conditioned
+        let tiny: ProjectiveTransform<f64> =
+            ProjectiveTransform::from_matrix(1, vec![1e-9, 0.0, 0.0, 1e-9]);
+        let inv = tiny.inverse().unwrap();
+        assert!((inv.matrix()[0] - 1e9).abs() < 1e-3);
+        // large and nearly singular
+        let flat = ProjectiveTransform::from_matrix(
+            2,
+            vec![1e10, 2e10, 0.0, 2e10, 4e10 + 1e-5, 0.0, 0.0, 0.0, 1.0],
+        );
+        assert!(flat.inverse().is_none());
+        // NaN values don't panic
+        let nan = ProjectiveTransform::from_matrix(1, vec![f64::NAN, 0.0, 0.0, 1.0]);
+        assert!(nan.inverse().is_none());
+    }
+    #[test]
+    fn projective_transform_compose_and_invert() {
+        use crate::projective::ProjectiveTransform;
+        let t = ProjectiveTransform::scaling(&Point::new_from_vec(&vec![2.0, 0.5, 4.0])).then(
+            &ProjectiveTransform::translation(&Point::new_from_vec(&vec![1.0, 2.0, 3.0])),
+        );
+        let p = Point::new_from_vec(&vec![1.0, 2.0, 0.5]);
+        assert_eq!(t.apply(&p).unwrap().get_vector(), &vec![3.0, 3.0, 5.0]);
+        let inv = t.inverse().unwrap();
+        assert!(inv.apply(&t.apply(&p).unwrap()).unwrap().close(&p, 1e-12));
+        assert!(t
+            .then(&inv)
+            .matrix()
+            .iter()
+            .zip(ProjectiveTransform::<f64>::identity(3).matrix())
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(
+            ProjectiveTransform::scaling(&Point::new_from_vec(&vec![1.0, 0.0]))
+                .inverse()
+                .is_none()
+        );
+        // a perspective divide: x -> 1 / x
+        let perspective = ProjectiveTransform::from_matrix(1, vec![0.0, 1.0, 1.0, 0.0]);
+        assert!(perspective
+            .apply(&Point::new_from_vec(&vec![0.0]))
+            .is_none());
+        assert_eq!(
+            perspective
+                .apply(&Point::new_from_vec(&vec![2.0]))
+                .unwrap()
+                .get_vector(),
+            &vec![0.5]
+        );
+    }
+    #[test]
+    fn pinhole_camera_project_unproject() {
+        use crate::projective::PinholeCamera;
+        // a camera at (0, 0, -5) looking along the world's z axis.
+        let camera = PinholeCamera::new(800.0, 800.0, 400.0, 300.0).with_pose(
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            &Point::new_from_vec(&vec![0.0, 0.0, 5.0]),
+        );
+        let world = Point::new_from_vec(&vec![1.0, 0.5, 5.0]);
+        let pixel = camera.project(&world).unwrap();
+        assert!(pixel.close(&Point::new_from_vec(&vec![480.0, 340.0]), 1e-9));
+        assert!(camera.unproject(&pixel, 10.0).close(&world, 1e-9));
+        assert!(camera
+            .project(&Point::new_from_vec(&vec![0.0, 0.0, -6.0]))
+            .is_none());
+        // a camera rotated by 90 degrees around the y axis, looking along the world's -x axis.
+        let turned = PinholeCamera::new(1.0, 1.0, 0.0, 0.0).with_pose(
+            [[0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [-1.0, 0.0, 0.0]],
+            &Point::new_from_vec(&vec![0.0, 0.0, 0.0]),
+        );
+        let world = Point::new_from_vec(&vec![-2.0, 1.0, 0.0]);
+        let pixel = turned.project(&world).unwrap();
+        assert!(pixel.close(&Point::new_from_vec(&vec![0.0, 0.5]), 1e-12));
+        assert!(turned.unproject(&pixel, 2.0).close(&world, 1e-12));
+    }
+    // random tests
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_samplers_are_seedable() {
+        use crate::random::UniformBall;
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let ball = UniformBall::new(&Point::new_from_vec(&vec![0.0, 0.0, 0.0]), 1.0);
+        let first: Vec<Point<f64>> = (&ball)
+            .sample_iter(StdRng::seed_from_u64(42))
+            .take(10)
+            .collect();
+        let second: Vec<Point<f64>> = (&ball)
+            .sample_iter(StdRng::seed_from_u64(42))
+            .take(10)
+            .collect();
+        assert_eq!(first, second);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_uniform_ball_and_sphere() {
+        use crate::random::{UniformBall, UniformBox, UniformSphere};
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(1);
+        let center: Point<f64> = Point::new_from_vec(&vec![1.0, -1.0]);
+        let n = 20000;
+        // in 2D, a quarter of the points are in the inner half of the radius.
+        let ball = UniformBall::new(&center, 2.0);
+        let inner = (0..n)
+            .map(|_| ball.sample(&mut rng).distance(&center))
+            .inspect(|d| assert!(*d <= 2.0))
+            .filter(|d| *d < 1.0)
+            .count();
+        assert!((inner as f64 / n as f64 - 0.25).abs() < 0.02);
+        let sphere = UniformSphere::new(&center, 2.0);
+        let mean = (0..n).fold(Point::new(2), |sum: Point<f64>, _| {
+            let p = sphere.sample(&mut rng);
+            assert!((p.distance(&center) - 2.0).abs() < 1e-12);
+            &sum + &p
+        });
+        assert!((&mean / &(n as f64)).close(&center, 0.05));
+        let min = Point::new_from_vec(&vec![0.0f32, 10.0]);
+        let max = Point::new_from_vec(&vec![1.0f32, 10.0]);
+        let p = UniformBox::new(&min, &max).sample(&mut rng);
+        assert!((0.0..=1.0).contains(p.x()) && *p.y() == 10.0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_ball_without_dimensions() {
+        let center: Point<f64> = Point::new_from_vec(&vec![]);
+        let _ = crate::random::UniformBall::new(&center, 1.0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_sphere_without_dimensions() {
+        let center: Point<f64> = Point::new_from_vec(&vec![]);
+        let _ = crate::random::UniformSphere::new(&center, 1.0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_ball_with_infinite_radius() {
+        let center: Point<f64> = Point::new_from_vec(&vec![0.0]);
+        let _ = crate::random::UniformBall::new(&center, f64::INFINITY);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_sphere_with_negative_radius() {
+        let center: Point<f64> = Point::new_from_vec(&vec![0.0, 0.0]);
+        let _ = crate::random::UniformSphere::new(&center, -1.0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_gaussian_with_negative_std_dev() {
+        let mean: Point<f64> = Point::new_from_vec(&vec![0.0, 0.0]);
+        let _ = crate::random::Gaussian::new(&mean, -1.0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_gaussian_with_nan_std_dev() {
+        let mean: Point<f64> = Point::new_from_vec(&vec![0.0]);
+        let _ = crate::random::Gaussian::new(&mean, f64::NAN);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_standard_simplex_without_dimensions() {
+        let _ = crate::random::UniformSimplex::<f64>::standard(0);
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_gaussians() {
+        use crate::random::{Gaussian, MultivariateGaussian};
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(2);
+        let n = 20000;
+        let gaussian = Gaussian::new(&Point::new_from_vec(&vec![5.0, -5.0]), 2.0);
+        let samples: Vec<Point<f64>> = (0..n).map(|_| gaussian.sample(&mut rng)).collect();
+        let variance = samples.iter().map(|p| (p.x() - 5.0).powi(2)).sum::<f64>() / n as f64;
+        assert!((variance - 4.0).abs() < 0.2);
+        let mean = Point::new_from_vec(&vec![0.0, 0.0]);
+        let covariance = vec![vec![4.0, 1.2], vec![1.2, 1.0]];
+        let gaussian = MultivariateGaussian::new(&mean, &covariance).unwrap();
+        let samples: Vec<Point<f64>> = (0..n).map(|_| gaussian.sample(&mut rng)).collect();
+        let cov = |i: usize, j: usize| {
+            samples
+                .iter()
+                .map(|p| p.get_value(i) * p.get_value(j))
+                .sum::<f64>()
+                / n as f64
+        };
+        assert!((cov(1, 1) - 4.0).abs() < 0.2);
+        assert!((cov(1, 2) - 1.2).abs() < 0.1);
+        assert!((cov(2, 2) - 1.0).abs() < 0.05);
+        assert!(MultivariateGaussian::new(&mean, &[vec![1.0, 0.0], vec![1.0, 1.0]]).is_none());
+    }
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_simplex() {
+        use crate::random::UniformSimplex;
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(3);
+        let triangle = UniformSimplex::new(&[
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![2.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 2.0]),
+        ]);
+        let n = 20000;
+        let mut sum = Point::new(2);
+        for _ in 0..n {
+            let p: Point<f64> = triangle.sample(&mut rng);
+            assert!(*p.x() >= 0.0 && *p.y() >= 0.0 && p.x() + p.y() <= 2.0 + 1e-12);
+            sum = &sum + &p;
+        }
+        // the centroid of the triangle
+        assert!((&sum / &(n as f64)).close(&Point::new_from_vec(&vec![2.0 / 3.0, 2.0 / 3.0]), 0.03));
+    }
+    // sequence tests
+    #[test]
+    fn sequence_halton() {
+        use crate::sequence::Halton;
+        let points: Vec<Point<f64>> = Halton::new(3).take(27).collect();
+        assert_eq!(points[0].get_vector(), &vec![0.0, 0.0, 0.0]);
+        assert_eq!(points[1].get_vector(), &vec![0.5, 1.0 / 3.0, 0.2]);
+        // the first 3^k points hit every interval of length 3^-k in base 3.
+        let mut cells: Vec<usize> = points
+            .iter()
+            .map(|p| (p.get_value(2) * 27.0 + 1e-9) as usize)
+            .collect();
+        cells.sort_unstable();
+        assert_eq!(cells, (0..27).collect::<Vec<usize>>());
+        // scrambling keeps the stratification, but changes the points.
+        let scrambled: Vec<Point<f64>> = Halton::scrambled(3, 99).take(27).collect();
+        let mut cells: Vec<usize> = scrambled
+            .iter()
+            .map(|p| (p.get_value(2) * 27.0 + 1e-9) as usize)
+            .collect();
+        cells.sort_unstable();
+        assert_eq!(cells, (0..27).collect::<Vec<usize>>());
+        assert!(scrambled != points);
+        assert!(Halton::scrambled(3, 1).nth(5) != Halton::scrambled(3, 2).nth(5));
+    }
+    #[test]
+    fn sequence_sobol_nets() {
+        use crate::sequence::{DirectionNumbers, Sobol};
+        let dim = Sobol::MAX_DIMENSION;
+        let points: Vec<Point<f64>> = Sobol::new(dim).unwrap().take(64).collect();
+        // each dimension is a (0, 6, 1)-net in base 2.
+        for d in 1..=dim {
+            let mut cells: Vec<usize> = points
+                .iter()
+                .map(|p| (p.get_value(d) * 64.0) as usize)
+                .collect();
+            cells.sort_unstable();
+            assert_eq!(cells, (0..64).collect::<Vec<usize>>());
+        }
+        // the first two dimensions are a (0, 6, 2)-net: every elementary box has one point.
+        for k in 0..=6 {
+            let mut cells: Vec<(usize, usize)> = points
+                .iter()
+                .map(|p| {
+                    (
+                        (p.get_value(1) * (1 << k) as f64) as usize,
+                        (p.get_value(2) * (1 << (6 - k)) as f64) as usize,
+                    )
+                })
+                .collect();
+            cells.sort_unstable();
+            cells.dedup();
+            assert_eq!(cells.len(), 64);
+        }
+        let custom = Sobol::with_direction_numbers(
+            2,
+            &[DirectionNumbers {
+                s: 1,
+                a: 0,
+                m: vec![1],
+            }],
+        )
+        .unwrap();
+        assert!(custom.take(10).eq(Sobol::new(2).unwrap().take(10)));
+        // quasi Monte Carlo integration of x * y * z over the unit cube.
+        let n = 1024;
+        let estimate = Sobol::new(3)
+            .unwrap()
+            .take(n)
+            .map(|p| p.get_vector().iter().product::<f64>())
+            .sum::<f64>()
+            / n as f64;
+        assert!((estimate - 0.125).abs() < 2e-3);
+    }
+    #[test]
+    fn sequence_sobol_errors() {
+        use crate::sequence::{DirectionNumbers, Sobol, SobolError};
+        assert_eq!(
+            Sobol::new(Sobol::MAX_DIMENSION + 1).unwrap_err(),
+            SobolError::TooManyDimensions { max: 21, found: 22 }
+        );
+        assert!(matches!(
+            Sobol::with_direction_numbers(3, &[]),
+            Err(SobolError::MissingDirectionNumbers {
+                expected: 2,
+                found: 0
+            })
+        ));
+        let even = DirectionNumbers {
+            s: 2,
+            a: 1,
+            m: vec![1, 2],
+        };
+        assert_eq!(
+            Sobol::with_direction_numbers(2, &[even]).unwrap_err(),
+            SobolError::InvalidDirectionNumbers { dimension: 2 }
+        );
+        let too_large = DirectionNumbers {
+            s: 40,
+            a: 0,
+            m: vec![1; 40],
+        };
+        assert!(Sobol::with_direction_numbers(2, &[too_large]).is_err());
+    }
+    #[test]
+    fn sequence_rd() {
+        use crate::sequence::Rd;
+        let points: Vec<Point<f64>> = Rd::new(2).take(100).collect();
+        assert_eq!(points[0].get_vector(), &vec![0.5, 0.5]);
+        assert!(points
+            .iter()
+            .all(|p| p.get_vector().iter().all(|v| (0.0..1.0).contains(v))));
+        // the plastic number is the generalized golden ratio for two dimensions.
+        let plastic = 1.324_717_957_244_746f64;
+        assert!((points[1].get_value(1) - (0.5 + 1.0 / plastic).fract()).abs() < 1e-12);
+        // no two of the first points are too close to each other.
+        for (i, p) in points.iter().enumerate() {
+            for q in &points[i + 1..] {
+                assert!(p.distance(q) > 0.02);
+            }
+        }
+    }
+    // space-filling curve tests
+    #[test]
+    fn morton_round_trip() {
+        use crate::space_filling::{morton_decode, morton_encode};
+        let p: Point<u64> = Point::new_from_vec(&vec![u64::MAX, 0]);
+        let index = morton_encode(&p).unwrap();
+        assert_eq!(index, 0xaaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa);
+        assert_eq!(morton_decode::<u64>(index, 2), Some(p));
+        // 3 dimensions of u64 have 42 bits each.
+        let p: Point<u64> = Point::new_from_vec(&vec![(1 << 42) - 1, 12345, 1 << 41]);
+        assert_eq!(morton_decode(morton_encode(&p).unwrap(), 3), Some(p));
+        assert_eq!(
+            morton_encode(&Point::<u64>::new_from_vec(&vec![1 << 42, 0, 0])),
+            None
+        );
+        assert_eq!(morton_decode::<u32>(1 << 64, 2), None);
+        // the first values of the Z-order in 2D.
+        let order: Vec<Vec<u32>> = (0..4)
+            .map(|i| morton_decode::<u32>(i, 2).unwrap().get_vector().clone())
+            .collect();
+        assert_eq!(order, vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]);
+    }
+    #[test]
+    fn hilbert_is_continuous() {
+        use crate::space_filling::{hilbert_decode, hilbert_encode};
+        for (dim, bits) in [(2usize, 3u32), (3, 3), (4, 2), (5, 1)].iter() {
+            let count = 1u128 << (*dim as u32 * bits);
+            let mut previous: Option<Point<u32>> = None;
+            for index in 0..count {
+                let p: Point<u32> = hilbert_decode(index, *dim, *bits).unwrap();
+                assert_eq!(hilbert_encode(&p, *bits), Some(index));
+                if let Some(q) = previous {
+                    // consecutive points are neighbours on the grid.
+                    let steps: u32 = p
+                        .get_vector()
+                        .iter()
+                        .zip(q.get_vector())
+                        .map(|(a, b)| a.abs_diff(*b))
+                        .sum();
+                    assert_eq!(steps, 1);
+                }
+                previous = Some(p);
+            }
+            assert_eq!(hilbert_decode::<u32>(count, *dim, *bits), None);
+        }
+        let p: Point<u64> = Point::new_from_vec(&vec![u64::MAX, 1]);
+        assert_eq!(
+            hilbert_decode(hilbert_encode(&p, 64).unwrap(), 2, 64),
+            Some(p)
+        );
+        assert_eq!(
+            hilbert_encode(&Point::<u32>::new_from_vec(&vec![8, 0]), 3),
+            None
+        );
+    }
+    #[test]
+    #[should_panic]
+    fn hilbert_too_many_bits() {
+        crate::space_filling::hilbert_encode(&Point::<u32>::new_from_vec(&vec![0, 0, 0, 0, 0]), 26);
+    }
+    #[test]
+    fn space_filling_sort() {
+        use crate::space_filling::{sort_by_hilbert, sort_by_morton};
+        let grid: Vec<Point<u32>> = (0..16)
+            .map(|i| Point::new_from_vec(&vec![i % 4, i / 4]))
+            .collect();
+        let mut points = grid.clone();
+        sort_by_morton(&mut points);
+        assert_eq!(
+            points[..4],
+            [
+                Point::new_from_vec(&vec![0, 0]),
+                Point::new_from_vec(&vec![0, 1]),
+                Point::new_from_vec(&vec![1, 0]),
+                Point::new_from_vec(&vec![1, 1]),
+            ]
+        );
+        let mut points = grid;
+        sort_by_hilbert(&mut points);
+        assert_eq!(points[0].get_vector(), &vec![0, 0]);
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0].get_vector(), pair[1].get_vector());
+            assert_eq!(a[0].abs_diff(b[0]) + a[1].abs_diff(b[1]), 1);
+        }
+    }
+    // voxel tests
+    #[test]
+    fn voxel_grid_cells() {
+        use crate::voxel::VoxelGrid;
+        let grid = VoxelGrid::new(&Point::new_from_vec(&vec![1.0, -1.0, 0.0]), 0.25);
+        let p = Point::new_from_vec(&vec![1.3, -1.3, 0.25]);
+        let cell = grid.cell_of(&p);
+        assert_eq!(cell.get_vector(), &vec![1, -2, 1]);
+        assert_eq!(grid.cell_min(&cell).get_vector(), &vec![1.25, -1.5, 0.25]);
+        assert_eq!(
+            grid.cell_center(&cell).get_vector(),
+            &vec![1.375, -1.375, 0.375]
+        );
+        assert_eq!(grid.cell_of(&grid.cell_center(&cell)), cell);
+    }
+    #[test]
+    #[should_panic]
+    fn voxel_grid_zero_cell_size() {
+        crate::voxel::VoxelGrid::new(&Point::new_from_vec(&vec![0.0]), 0.0);
+    }
+    #[test]
+    fn voxel_cell_map_matches_brute_force() {
+        use crate::sequence::Halton;
+        use crate::voxel::{CellMap, VoxelGrid};
+        let points: Vec<Point<f64>> = Halton::new(3).skip(1).take(500).collect();
+        let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0, 0.0]), 0.1);
+        let map = CellMap::from_points(grid, &points);
+        assert_eq!(map.len(), 500);
+        assert_eq!(map.cells().map(|(_, v)| v.len()).sum::<usize>(), 500);
+        for query in Halton::scrambled(3, 5).take(20) {
+            for distance in [0.05, 0.15, 0.3].iter() {
+                let mut found = map.within_distance(&points, &query, *distance);
+                found.sort_unstable();
+                let expected: Vec<usize> = (0..points.len())
+                    .filter(|i| points[*i].distance(&query) <= *distance)
+                    .collect();
+                assert_eq!(found, expected);
+            }
+        }
+        // one cell away in each dimension is 27 cells in 3D.
+        let cell = map.grid().cell_of(&points[0]);
+        assert!(map.get(&cell).contains(&0));
+        let around: usize = (0..27)
+            .map(|i| {
+                let offset = vec![i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1];
+                map.get(&(&cell + &Point::new_from_vec(&offset))).len()
+            })
+            .sum();
+        assert_eq!(map.neighbours(&points[0], 1).len(), around);
+    }
+    #[test]
+    fn voxel_cell_map_huge_reach() {
+        use crate::voxel::{CellMap, VoxelGrid};
+        let points: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.5, 0.5]),
+            Point::new_from_vec(&vec![-3.0, 7.0]),
+            Point::new_from_vec(&vec![1e12, -1e12]),
+        ];
+        let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0);
+        let map = CellMap::from_points(grid, &points);
+        let query = Point::new_from_vec(&vec![0.0, 0.0]);
+        assert_eq!(map.neighbours(&query, usize::MAX).len(), 3);
+        assert_eq!(map.neighbours(&query, 1 << 30).len(), 2);
+        let mut found = map.within_distance(&points, &query, 1e300);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2]);
+        let mut found = map.within_distance(&points, &query, 10.0);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1]);
+    }
+    #[test]
+    #[should_panic]
+    fn voxel_cell_map_infinite_distance() {
+        use crate::voxel::{CellMap, VoxelGrid};
+        let points: Vec<Point<f64>> = vec![Point::new_from_vec(&vec![0.5])];
+        let map = CellMap::from_points(
+            VoxelGrid::new(&Point::new_from_vec(&vec![0.0]), 1.0),
+            &points,
+        );
+        map.within_distance(&points, &points[0], f64::INFINITY);
+    }
+    #[test]
+    #[should_panic]
+    fn voxel_grid_nan_point() {
+        let grid = crate::voxel::VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0);
+        grid.cell_of(&Point::new_from_vec(&vec![0.0, f64::NAN]));
+    }
+    #[test]
+    fn voxel_downsample() {
+        use crate::voxel::{downsample, Representative, VoxelGrid};
+        let points: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.5, 0.5]),
+            Point::new_from_vec(&vec![-0.5, 0.5]),
+            Point::new_from_vec(&vec![0.7, 0.1]),
+            Point::new_from_vec(&vec![-0.1, 0.9]),
+            Point::new_from_vec(&vec![0.3, 0.9]),
+        ];
+        let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0);
+        let first = downsample(&grid, &points, Representative::First);
+        assert_eq!(first, vec![points[0].clone(), points[1].clone()]);
+        let centroids = downsample(&grid, &points, Representative::Centroid);
+        assert!(centroids[0].close(&Point::new_from_vec(&vec![0.5, 0.5]), 1e-12));
+        assert!(centroids[1].close(&Point::new_from_vec(&vec![-0.3, 0.7]), 1e-12));
+        assert!(downsample(&grid, &[], Representative::Centroid).is_empty());
+    }
+    // stats tests
+    #[test]
+    fn stats_moments() {
+        use crate::stats::{centroid, covariance, max, min, variance, Accumulator};
+        let points: Vec<Point<f64>> = (0..50)
+            .map(|i| {
+                let t = i as f64;
+                Point::new_from_vec(&vec![t, (t * 0.7).sin(), 3.0 - 2.0 * t])
+            })
+            .collect();
+        let n = points.len() as f64;
+        // two-pass reference values
+        let mean: Vec<f64> = (1..=3)
+            .map(|d| points.iter().map(|p| p.get_value(d)).sum::<f64>() / n)
+            .collect();
+        let cov = |i: usize, j: usize| {
+            points
+                .iter()
+                .map(|p| (p.get_value(i + 1) - mean[i]) * (p.get_value(j + 1) - mean[j]))
+                .sum::<f64>()
+                / n
+        };
+        assert!(centroid(&points)
+            .unwrap()
+            .close(&Point::new_from_vec(&mean), 1e-12));
+        let c = covariance(&points).unwrap();
+        for (i, row) in c.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                assert!((value - cov(i, j)).abs() < 1e-9);
+            }
+        }
+        assert!(variance(&points).unwrap().close(
+            &Point::new_from_vec(&vec![cov(0, 0), cov(1, 1), cov(2, 2)]),
+            1e-9
+        ));
+        let min_y = points.iter().map(|p| *p.y()).fold(f64::INFINITY, f64::min);
+        assert_eq!(min(&points).unwrap().get_vector(), &vec![0.0, min_y, -95.0]);
+        assert_eq!(max(&points).unwrap().get_value(1), &49.0);
+        let mut acc = Accumulator::new(3);
+        acc.extend(&points);
+        let sample = acc.sample_variance().unwrap();
+        assert!((sample.get_value(1) - cov(0, 0) * n / (n - 1.0)).abs() < 1e-9);
+        assert_eq!(acc.count(), 50);
+        // not enough points
+        let empty: Vec<Point<f64>> = Vec::new();
+        assert!(centroid(&empty).is_none());
+        let mut acc = Accumulator::new(1);
+        acc.push(&Point::new_from_vec(&vec![1.0]));
+        assert_eq!(acc.variance().unwrap().get_vector(), &vec![0.0]);
+        assert!(acc.sample_variance().is_none());
+    }
+    #[test]
+    fn stats_welford_is_stable() {
+        use crate::stats::variance;
+        // a naive sum of squares loses all the precision with this offset.
+        let points: Vec<Point<f64>> = [4.0, 7.0, 13.0, 16.0]
+            .iter()
+            .map(|v| Point::new_from_vec(&vec![1e9 + v]))
+            .collect();
+        assert!((variance(&points).unwrap().get_value(1) - 22.5).abs() < 1e-6);
+    }
+    #[test]
+    fn stats_weights() {
+        use crate::stats::{weighted_centroid, Accumulator};
+        let points: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![1.0, 2.0]),
+            Point::new_from_vec(&vec![5.0, -2.0]),
+            Point::new_from_vec(&vec![0.0, 0.0]),
+        ];
+        let weights = [2.0, 3.0, 0.0];
+        let mut weighted = Accumulator::new(2);
+        let mut repeated = Accumulator::new(2);
+        for (p, w) in points.iter().zip(weights.iter()) {
+            weighted.push_weighted(p, *w);
+            for _ in 0..*w as usize {
+                repeated.push(p);
+            }
+        }
+        assert!(weighted
+            .mean()
+            .unwrap()
+            .close(&repeated.mean().unwrap(), 1e-12));
+        assert!(weighted
+            .sample_variance()
+            .unwrap()
+            .close(&repeated.sample_variance().unwrap(), 1e-12));
+        // a point with weight 0 still counts for the minimum.
+        assert_eq!(weighted.min().unwrap().get_vector(), &vec![0.0, -2.0]);
+        assert_eq!(
+            weighted_centroid(&points, &weights).unwrap(),
+            weighted.mean().unwrap()
+        );
+        assert!(weighted_centroid(&points, &[0.0, 0.0, 0.0]).is_none());
+    }
+    #[test]
+    fn stats_quantiles() {
+        use crate::stats::{median, quantile, quantiles};
+        let points: Vec<Point<f32>> = [3.0, f32::NAN, 1.0, 2.0]
+            .iter()
+            .map(|v| Point::new_from_vec(&vec![*v, -v]))
+            .collect();
+        // NaN values are the greatest.
+        assert_eq!(
+            quantile(&points, 0.0).unwrap().get_vector(),
+            &vec![1.0, -3.0]
+        );
+        assert!(quantile(&points, 1.0).unwrap().get_value(1).is_nan());
+        let qs = quantiles(&points[..1], &[0.0, 0.5, 1.0]).unwrap();
+        assert!(qs.iter().all(|q| q.get_vector() == &vec![3.0, -3.0]));
+        let odd = [&points[0], &points[2], &points[3]];
+        let odd: Vec<Point<f32>> = odd.iter().map(|p| (*p).clone()).collect();
+        assert_eq!(median(&odd).unwrap().get_vector(), &vec![2.0, -2.0]);
+        assert!(median::<f64>(&[]).is_none());
+    }
+    #[test]
+    fn stats_geometric_median() {
+        use crate::stats::{geometric_median, WeiszfeldOptions};
+        let options = WeiszfeldOptions::new()
+            .tolerance(1e-12)
+            .max_iterations(10000);
+        // on a line, the geometric median is the median, which is one of the points.
+        let line: Vec<Point<f64>> = [0.0, 1.0, 2.0, 10.0, 20.0]
+            .iter()
+            .map(|t| Point::new_from_vec(&vec![*t, 2.0 * t]))
+            .collect();
+        let median = geometric_median(&line, &options).unwrap();
+        assert!(median.converged);
+        assert!(median.point.close(&line[2], 1e-6));
+        // a point repeated by a majority is the median.
+        let mut points: Vec<Point<f64>> = vec![Point::new_from_vec(&vec![1.0, 1.0]); 3];
+        points.push(Point::new_from_vec(&vec![5.0, -3.0]));
+        points.push(Point::new_from_vec(&vec![-4.0, 8.0]));
+        let median = geometric_median(&points, &options).unwrap();
+        assert!(median.point.close(&points[0], 1e-9));
+        // the sum of distances is minimal.
+        let triangle: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![4.0, 0.0]),
+            Point::new_from_vec(&vec![1.0, 3.0]),
+        ];
+        let median = geometric_median(&triangle, &options).unwrap().point;
+        let cost = |p: &Point<f64>| triangle.iter().map(|q| q.distance(p)).sum::<f64>();
+        for offset in [[1e-4, 0.0], [-1e-4, 0.0], [0.0, 1e-4], [0.0, -1e-4]].iter() {
+            let moved = &median + &Point::new_from_vec(&offset.to_vec());
+            assert!(cost(&median) < cost(&moved));
+        }
+        let limited = geometric_median(&triangle, &options.max_iterations(1)).unwrap();
+        assert!(!limited.converged);
+        assert_eq!(limited.iterations, 1);
+        assert!(geometric_median::<f64>(&[], &options).is_none());
+    }
+    #[test]
+    fn stats_geometric_median_tiny_values() {
+        use crate::stats::{geometric_median, WeiszfeldOptions};
+        // the tolerance is absolute, so it is scaled with the data.
+        let options = WeiszfeldOptions::new()
+            .tolerance(1e-32)
+            .max_iterations(10000);
+        let points: Vec<Point<f64>> = [
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [0.0, 1.0],
+            [1.0, 1.0],
+            [100.0, 100.0],
+        ]
+        .iter()
+        .map(|v| Point::new_from_vec(&vec![v[0] * 1e-20, v[1] * 1e-20]))
+        .collect();
+        let median = geometric_median(&points, &options).unwrap();
+        assert!(median.converged);
+        assert!(median.iterations > 1);
+        // on the diagonal, where the angles to the square's corners balance the outlier.
+        let expected = (0.5 + 0.5 / 3.0_f64.sqrt()) * 1e-20;
+        assert!(median
+            .point
+            .close(&Point::new_from_vec(&vec![expected, expected]), 1e-30));
+    }
+    #[test]
+    fn stats_trimmed_mean() {
+        use crate::stats::trimmed_mean;
+        let points: Vec<Point<f64>> = (0..10)
+            .map(|i| Point::new_from_vec(&vec![i as f64, if i == 3 { 1e6 } else { 1.0 }]))
+            .collect();
+        assert_eq!(
+            trimmed_mean(&points, 0.1).unwrap().get_vector(),
+            &vec![4.5, 1.0]
+        );
+        assert_eq!(
+            trimmed_mean(&points, 0.0).unwrap().get_vector(),
+            &vec![4.5, 1.0 + 999_999.0 / 10.0]
+        );
+        assert!(trimmed_mean::<f64>(&[], 0.2).is_none());
+    }
+    #[test]
+    #[should_panic]
+    fn stats_quantile_out_of_range() {
+        crate::stats::quantile(&[Point::new_from_vec(&vec![1.0])], 1.5);
+    }
+    // delaunay tests
+    fn simplex_volume(points: &[Point<f64>], simplex: &[usize]) -> f64 {
+        let last = points[simplex[simplex.len() - 1]].get_vector();
+        let rows: Vec<Vec<f64>> = simplex[..simplex.len() - 1]
+            .iter()
+            .map(|v| {
+                let p = points[*v].get_vector();
+                p.iter().zip(last).map(|(a, b)| a - b).collect()
+            })
+            .collect();
+        let det = if rows.len() == 2 {
+            rows[0][0] * rows[1][1] - rows[0][1] * rows[1][0]
+        } else {
+            rows[0][0] * (rows[1][1] * rows[2][2] - rows[1][2] * rows[2][1])
+                - rows[0][1] * (rows[1][0] * rows[2][2] - rows[1][2] * rows[2][0])
+                + rows[0][2] * (rows[1][0] * rows[2][1] - rows[1][1] * rows[2][0])
+        };
+        det / if rows.len() == 2 { 2.0 } else { 6.0 }
+    }
+    fn check_delaunay(triangulation: &crate::delaunay::Delaunay, hull_volume: f64) {
+        use crate::predicates::insphere;
+        use std::cmp::Ordering;
+        let points = triangulation.points();
+        let mut volume = 0.0;
+        for (s, simplex) in triangulation.simplices().iter().enumerate() {
+            let v = simplex_volume(points, simplex);
+            assert!(v > 0.0);
+            volume += v;
+            let coordinates: Vec<&[f64]> = simplex
+                .iter()
+                .map(|v| points[*v].get_vector().as_slice())
+                .collect();
+            for p in points {
+                assert!(insphere(&coordinates, p.get_vector()) != Ordering::Greater);
+            }
+            for (i, n) in triangulation.neighbours()[s].iter().enumerate() {
+                if let Some(n) = n {
+                    let other = &triangulation.simplices()[*n];
+                    let shared = simplex.iter().filter(|v| other.contains(v)).count();
+                    assert_eq!(shared, simplex.len() - 1);
+                    assert!(!other.contains(&simplex[i]));
+                    assert!(triangulation.neighbours()[*n].contains(&Some(s)));
+                }
+            }
+        }
+        assert!((volume - hull_volume).abs() < 1e-9 * hull_volume.max(1.0));
+    }
+    #[test]
+    fn delaunay_2d() {
+        use crate::delaunay::Delaunay;
+        use crate::sequence::Halton;
+        let mut points: Vec<Point<f64>> = Halton::scrambled(2, 11).skip(1).take(300).collect();
+        for corner in [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]].iter() {
+            points.push(Point::new_from_vec(&corner.to_vec()));
+        }
+        let triangulation = Delaunay::new(&points);
+        check_delaunay(&triangulation, 1.0);
+        // Euler: 2n - 2 - h triangles, for n points with h on the hull.
+        let hull = triangulation.hull_facets().len();
+        assert_eq!(triangulation.simplices().len(), 2 * points.len() - 2 - hull);
+        for query in Halton::new(2).skip(1).take(50) {
+            let query = &(&query * &1.2) - &0.1;
+            match triangulation.locate(&query) {
+                Some(s) => {
+                    let simplex = &triangulation.simplices()[s];
+                    let min: Vec<f64> = (0..2)
+                        .map(|d| {
+                            simplex
+                                .iter()
+                                .map(|v| points[*v].get_vector()[d])
+                                .fold(f64::INFINITY, f64::min)
+                        })
+                        .collect();
+                    assert!(query.x() >= &(min[0] - 1e-12) && query.y() >= &(min[1] - 1e-12));
+                }
+                None => assert!(query.get_vector().iter().any(|v| *v < 0.0 || *v > 1.0)),
+            }
+        }
+    }
+    #[test]
+    fn delaunay_degenerate_inputs() {
+        use crate::delaunay::Delaunay;
+        // a grid: every square has 4 cocircular points.
+        let grid: Vec<Point<f64>> = (0..100)
+            .map(|i| Point::new_from_vec(&vec![(i % 10) as f64, (i / 10) as f64]))
+            .collect();
+        let triangulation = Delaunay::new(&grid);
+        check_delaunay(&triangulation, 81.0);
+        assert_eq!(triangulation.simplices().len(), 162);
+        // the vertices of the grid are located in a simplex that has them.
+        let s = triangulation.locate(&grid[55]).unwrap();
+        let coordinates: Vec<&Point<f64>> = triangulation.simplices()[s]
+            .iter()
+            .map(|v| &grid[*v])
+            .collect();
+        assert!(coordinates.iter().any(|p| p.distance(&grid[55]) <= 1.0));
+        // duplicated points
+        let mut duplicated = grid.clone();
+        duplicated.extend(grid.iter().cloned());
+        assert_eq!(Delaunay::new(&duplicated).simplices().len(), 162);
+        // collinear points
+        let line: Vec<Point<f64>> = (0..5)
+            .map(|i| Point::new_from_vec(&vec![i as f64, 2.0 * i as f64]))
+            .collect();
+        assert!(Delaunay::new(&line).simplices().is_empty());
+        assert!(Delaunay::new(&[]).simplices().is_empty());
+    }
+    #[test]
+    fn delaunay_huge_coordinates() {
+        use crate::delaunay::Delaunay;
+        // the products of the differences overflow.
+        let values = [
+            [0.0, 0.0],
+            [1e200, 0.0],
+            [0.0, 1e200],
+            [1e200, 1e200],
+            [3e199, 7e199],
+        ];
+        let huge: Vec<Point<f64>> = values
+            .iter()
+            .map(|v| Point::new_from_vec(&v.to_vec()))
+            .collect();
+        // the same points scaled by a power of 2 have the same triangulation.
+        let factor = 2.0_f64.powi(-660);
+        let small: Vec<Point<f64>> = values
+            .iter()
+            .map(|v| Point::new_from_vec(&vec![v[0] * factor, v[1] * factor]))
+            .collect();
+        let triangulation = Delaunay::new(&huge);
+        assert_eq!(triangulation.simplices().len(), 4);
+        assert_eq!(triangulation.simplices(), Delaunay::new(&small).simplices());
+    }
+    #[test]
+    fn delaunay_3d() {
+        use crate::delaunay::Delaunay;
+        use crate::sequence::Halton;
+        let mut points: Vec<Point<f64>> = Halton::scrambled(3, 4).skip(1).take(200).collect();
+        for i in 0..8 {
+            points.push(Point::new_from_vec(&vec![
+                (i & 1) as f64,
+                (i >> 1 & 1) as f64,
+                (i >> 2) as f64,
+            ]));
+        }
+        let triangulation = Delaunay::new(&points);
+        check_delaunay(&triangulation, 1.0);
+        for query in Halton::new(3).skip(1).take(30) {
+            assert!(triangulation.locate(&query).is_some());
+        }
+        assert!(triangulation
+            .locate(&Point::new_from_vec(&vec![0.5, 0.5, 1.5]))
+            .is_none());
+        // the hull of the cube is 12 triangles.
+        assert_eq!(triangulation.hull_facets().len(), 12);
+        // a grid of cospherical points
+        let grid: Vec<Point<f64>> = (0..64)
+            .map(|i| {
+                Point::new_from_vec(&vec![(i % 4) as f64, (i / 4 % 4) as f64, (i / 16) as f64])
+            })
+            .collect();
+        check_delaunay(&Delaunay::new(&grid), 27.0);
+    }
+    #[test]
+    fn predicates_are_exact() {
+        use crate::predicates::{insphere, orient};
+        use std::cmp::Ordering;
+        let (b, c) = ([12.0, 12.0], [24.0, 24.0]);
+        assert_eq!(orient(&[&[0.5, 0.5], &b, &c]), Ordering::Equal);
+        // too close to the line for floating point arithmetic.
+        let delta = f64::EPSILON / 2.0;
+        assert_eq!(orient(&[&[0.5, 0.5 + delta], &b, &c]), Ordering::Greater);
+        assert_eq!(orient(&[&[0.5 + delta, 0.5], &b, &c]), Ordering::Less);
+        let triangle: [&[f64]; 3] = [&[0.0, 0.0], &[1.0, 0.0], &[0.0, 1.0]];
+        assert_eq!(insphere(&triangle, &[1.0, 1.0]), Ordering::Equal);
+        assert_eq!(insphere(&triangle, &[1.0, 1.0 - delta]), Ordering::Greater);
+        assert_eq!(
+            insphere(&triangle, &[1.0, 1.0 + 2.0 * delta]),
+            Ordering::Less
+        );
+        let tetrahedron: [&[f64]; 4] = [
+            &[0.0, 0.0, 0.0],
+            &[0.0, 1.0, 0.0],
+            &[1.0, 0.0, 0.0],
+            &[0.0, 0.0, 1.0],
+        ];
+        assert_eq!(orient(&tetrahedron), Ordering::Greater);
+        assert_eq!(insphere(&tetrahedron, &[1.0, 1.0, 1.0]), Ordering::Equal);
+        assert_eq!(insphere(&tetrahedron, &[0.5, 0.5, 0.5]), Ordering::Greater);
+        assert_eq!(
+            insphere(&tetrahedron, &[1.0, 1.0, 1.0 + 4.0 * delta]),
+            Ordering::Less
+        );
+    }
+    // voronoi tests
+    fn check_voronoi(points: &[Point<f64>], voronoi: &crate::voronoi::Voronoi, box_volume: f64) {
+        let total: f64 = voronoi.cells().iter().map(|c| c.measure()).sum();
+        assert!((total - box_volume).abs() < 1e-9 * box_volume);
+        for cell in voronoi.cells() {
+            let site = &points[cell.site()];
+            assert!(cell.measure() >= 0.0);
+            // the vertices are closest to the site.
+            for v in cell.vertices() {
+                let d = v.distance(site);
+                assert!(points.iter().all(|p| p.distance(v) >= d - 1e-9));
+            }
+            for n in cell.neighbours() {
+                assert!(voronoi.cells()[n].neighbours().contains(&cell.site()));
+            }
+            assert_eq!(cell.faces().len(), cell.face_neighbours().len());
+        }
+    }
+    #[test]
+    fn voronoi_2d() {
+        use crate::sequence::Halton;
+        use crate::voronoi::Voronoi;
+        let points: Vec<Point<f64>> = Halton::scrambled(2, 3).skip(1).take(200).collect();
+        let min = Point::new_from_vec(&vec![-0.5, 0.0]);
+        let max = Point::new_from_vec(&vec![1.0, 2.0]);
+        let voronoi = Voronoi::new(&points, &min, &max);
+        check_voronoi(&points, &voronoi, 3.0);
+        // the cells of a grid are squares with 4 neighbours (8 points around each square are
+        // cocircular).
+        let grid: Vec<Point<f64>> = (0..25)
+            .map(|i| Point::new_from_vec(&vec![(i % 5) as f64, (i / 5) as f64]))
+            .collect();
+        let voronoi = Voronoi::new(
+            &grid,
+            &Point::new_from_vec(&vec![-0.5, -0.5]),
+            &Point::new_from_vec(&vec![4.5, 4.5]),
+        );
+        check_voronoi(&grid, &voronoi, 25.0);
+        let center = &voronoi.cells()[12];
+        assert!((center.measure() - 1.0).abs() < 1e-12);
+        assert_eq!(center.vertices().len(), 4);
+        assert_eq!(center.neighbours(), vec![7, 11, 13, 17]);
+        assert_eq!(voronoi.cells()[0].neighbours(), vec![1, 5]);
+        assert_eq!(
+            voronoi.cells()[0]
+                .face_neighbours()
+                .iter()
+                .filter(|n| n.is_none())
+                .count(),
+            2
+        );
+    }
+    #[test]
+    fn voronoi_degenerate_2d() {
+        use crate::voronoi::Voronoi;
+        let min = Point::new_from_vec(&vec![0.0, 0.0]);
+        let max = Point::new_from_vec(&vec![4.0, 1.0]);
+        // collinear points split the box in strips.
+        let line: Vec<Point<f64>> = (0..4)
+            .map(|i| Point::new_from_vec(&vec![i as f64 + 0.5, 0.5]))
+            .collect();
+        let voronoi = Voronoi::new(&line, &min, &max);
+        check_voronoi(&line, &voronoi, 4.0);
+        assert!(voronoi
+            .cells()
+            .iter()
+            .all(|c| (c.measure() - 1.0).abs() < 1e-12));
+        assert_eq!(voronoi.cells()[1].neighbours(), vec![0, 2]);
+        // a duplicated point has an empty cell.
+        let mut duplicated = line.clone();
+        duplicated.push(line[2].clone());
+        let voronoi = Voronoi::new(&duplicated, &min, &max);
+        assert!(voronoi.cells()[4].is_empty());
+        assert!((voronoi.cells()[2].measure() - 1.0).abs() < 1e-12);
+        // only the nearest sites along the line bound a cell, in any order of the sites.
+        let shuffled: Vec<Point<f64>> = [3, 0, 2, 1, 0].iter().map(|i| line[*i].clone()).collect();
+        let voronoi = Voronoi::new(&shuffled, &min, &max);
+        check_voronoi(&shuffled, &voronoi, 4.0);
+        assert_eq!(voronoi.cells()[0].neighbours(), vec![2]);
+        assert_eq!(voronoi.cells()[1].neighbours(), vec![3]);
+        assert_eq!(voronoi.cells()[2].neighbours(), vec![0, 3]);
+        assert_eq!(voronoi.cells()[3].neighbours(), vec![1, 2]);
+        assert!(voronoi.cells()[4].is_empty());
+        // -0.0 is the same site as 0.0
+        let sites: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.0, 0.5]),
+            Point::new_from_vec(&vec![2.0, 0.5]),
+            Point::new_from_vec(&vec![1.0, 0.9]),
+            Point::new_from_vec(&vec![-0.0, 0.5]),
+        ];
+        let voronoi = Voronoi::new(&sites, &Point::new_from_vec(&vec![-1.0, 0.0]), &max);
+        assert!(!voronoi.cells()[0].is_empty());
+        assert!(voronoi.cells()[3].is_empty());
+    }
+    #[test]
+    fn voronoi_3d() {
+        use crate::sequence::Halton;
+        use crate::voronoi::Voronoi;
+        let points: Vec<Point<f64>> = Halton::scrambled(3, 8).skip(1).take(100).collect();
+        let min = Point::new_from_vec(&vec![0.0, 0.0, 0.0]);
+        let max = Point::new_from_vec(&vec![1.0, 1.0, 1.0]);
+        let voronoi = Voronoi::new(&points, &min, &max);
+        check_voronoi(&points, &voronoi, 1.0);
+        // the cells of a grid are cubes with 6 neighbours.
+        let grid: Vec<Point<f64>> = (0..27)
+            .map(|i| Point::new_from_vec(&vec![(i % 3) as f64, (i / 3 % 3) as f64, (i / 9) as f64]))
+            .collect();
+        let voronoi = Voronoi::new(
+            &grid,
+            &Point::new_from_vec(&vec![-0.5, -0.5, -0.5]),
+            &Point::new_from_vec(&vec![2.5, 2.5, 2.5]),
+        );
+        check_voronoi(&grid, &voronoi, 27.0);
+        let center = &voronoi.cells()[13];
+        assert!((center.measure() - 1.0).abs() < 1e-12);
+        assert_eq!(center.vertices().len(), 8);
+        assert_eq!(center.faces().len(), 6);
+        assert_eq!(center.neighbours(), vec![4, 10, 12, 14, 16, 22]);
+        // collinear points split the box in slabs.
+        let line: Vec<Point<f64>> = [2.0, 0.0, 1.0]
+            .iter()
+            .map(|t| Point::new_from_vec(&vec![*t, *t, *t]))
+            .collect();
+        let voronoi = Voronoi::new(
+            &line,
+            &Point::new_from_vec(&vec![-0.5, -0.5, -0.5]),
+            &Point::new_from_vec(&vec![2.5, 2.5, 2.5]),
+        );
+        check_voronoi(&line, &voronoi, 27.0);
+        assert_eq!(voronoi.cells()[2].neighbours(), vec![0, 1]);
+        assert_eq!(voronoi.cells()[0].neighbours(), vec![2]);
+    }
+    // simplex tests
+    #[test]
+    fn simplex_volume_and_barycentric() {
+        use crate::simplex::Simplex;
+        let tetrahedron: Simplex<f64> = Simplex::new(&[
+            Point::new_from_vec(&vec![0.0, 0.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 2.0, 0.0]),
+            Point::new_from_vec(&vec![2.0, 0.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 0.0, 2.0]),
+        ]);
+        assert!((tetrahedron.signed_volume() - 8.0 / 6.0).abs() < 1e-12);
+        assert!((tetrahedron.volume() - 8.0 / 6.0).abs() < 1e-12);
+        let p = Point::new_from_vec(&vec![0.5, 0.25, 0.5]);
+        let coordinates = tetrahedron.barycentric(&p).unwrap();
+        assert!((coordinates.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(tetrahedron.from_barycentric(&coordinates).close(&p, 1e-12));
+        assert!(tetrahedron.contains(&p));
+        assert!(tetrahedron.contains(&Point::new_from_vec(&vec![1.0, 1.0, 0.0])));
+        assert!(!tetrahedron.contains(&Point::new_from_vec(&vec![1.0, 1.0, 0.1])));
+        let segment = Simplex::new(&[
+            Point::new_from_vec(&vec![1.0]),
+            Point::new_from_vec(&vec![3.0]),
+        ]);
+        // the determinant of the rows `v_i - v_N` is negative for increasing segments.
+        assert_eq!(segment.signed_volume(), -2.0);
+        assert_eq!(segment.volume(), 2.0);
+        assert_eq!(
+            segment.barycentric(&Point::new_from_vec(&vec![2.5])),
+            Some(vec![0.25, 0.75])
+        );
+        assert_eq!(segment.circumradius(), Some(1.0));
+        assert_eq!(segment.incenter().get_vector(), &vec![2.0]);
+        // a degenerate triangle
+        let flat = Simplex::new(&[
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![1.0, 1.0]),
+            Point::new_from_vec(&vec![2.0, 2.0]),
+        ]);
+        assert_eq!(flat.volume(), 0.0);
+        assert!(flat
+            .barycentric(&Point::new_from_vec(&vec![1.0, 1.0]))
+            .is_none());
+        assert!(flat.circumcenter().is_none());
+        assert!(!flat.contains(&Point::new_from_vec(&vec![1.0, 1.0])));
+    }
+    #[test]
+    fn simplex_centers() {
+        use crate::simplex::Simplex;
+        let vertices: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.1, 0.0, 0.3, 0.0]),
+            Point::new_from_vec(&vec![1.0, 0.2, 0.0, 0.5]),
+            Point::new_from_vec(&vec![0.0, 1.5, 0.0, 0.1]),
+            Point::new_from_vec(&vec![0.3, 0.0, 1.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 0.4, 0.2, 2.0]),
+        ];
+        let simplex = Simplex::new(&vertices);
+        let center = simplex.circumcenter().unwrap();
+        let radius = simplex.circumradius().unwrap();
+        assert!(vertices
+            .iter()
+            .all(|v| (v.distance(&center) - radius).abs() < 1e-12));
+        // the incenter is at the inradius from every edge of a triangle.
+        let triangle: Simplex<f64> = Simplex::new(&[
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![7.0, 1.0]),
+            Point::new_from_vec(&vec![2.0, 5.0]),
+        ]);
+        let incenter = triangle.incenter();
+        let inradius = triangle.inradius();
+        assert!(triangle.contains(&incenter));
+        let v = triangle.vertices();
+        for i in 0..3 {
+            let (a, b) = (&v[i], &v[(i + 1) % 3]);
+            let edge = a - b;
+            let area = Simplex::new(&[a.clone(), b.clone(), incenter.clone()]).volume();
+            assert!((2.0 * area / edge.norm() - inradius).abs() < 1e-12);
+        }
+    }
+    #[test]
+    fn simplex_interpolation() {
+        use crate::delaunay::Delaunay;
+        use crate::simplex::Simplex;
+        let triangle = Simplex::new(&[
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![2.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 2.0]),
+        ]);
+        // a linear function is interpolated exactly.
+        let f = |p: &Point<f64>| 3.0 * p.x() - 2.0 * p.y() + 1.0;
+        let values: Vec<f64> = triangle.vertices().iter().map(f).collect();
+        let p = Point::new_from_vec(&vec![0.3, 1.1]);
+        assert!((triangle.interpolate(&p, &values).unwrap() - f(&p)).abs() < 1e-12);
+        // vector values
+        let colors: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![1.0, 0.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 1.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 0.0, 1.0]),
+        ];
+        let color = triangle
+            .interpolate(&Point::new_from_vec(&vec![1.0, 0.5]), &colors)
+            .unwrap();
+        assert!(color.close(&Point::new_from_vec(&vec![0.25, 0.5, 0.25]), 1e-12));
+        // the simplices of a triangulation
+        let points: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![1.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 1.0]),
+            Point::new_from_vec(&vec![1.0, 1.2]),
+        ];
+        let triangulation = Delaunay::new(&points);
+        let query = Point::new_from_vec(&vec![0.7, 0.6]);
+        let simplex = triangulation.simplex(triangulation.locate(&query).unwrap());
+        assert!(simplex.contains(&query) && simplex.signed_volume() > 0.0);
+        // the orientation agrees with the tetrahedra of a triangulation
+        let points: Vec<Point<f64>> = (0..20)
+            .map(|i| {
+                let t = i as f64;
+                Point::new_from_vec(&vec![(t * 0.7).sin(), (t * 1.3).cos(), (t * 0.37).sin()])
+            })
+            .collect();
+        let triangulation = Delaunay::new(&points);
+        assert!(!triangulation.simplices().is_empty());
+        assert!((0..triangulation.simplices().len())
+            .all(|s| triangulation.simplex(s).signed_volume() > 0.0));
+    }
+    // hyperplane tests
+    #[test]
+    fn hyperplane_from_points() {
+        use crate::hyperplane::{Hyperplane, Side};
+        use crate::simplex::Simplex;
+        let points: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![1.0, 0.2, 0.0, 0.5]),
+            Point::new_from_vec(&vec![0.0, 1.5, 0.0, 0.1]),
+            Point::new_from_vec(&vec![0.3, 0.0, 1.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 0.4, 0.2, 2.0]),
+        ];
+        let plane = Hyperplane::from_points(&points).unwrap();
+        assert!((plane.normal().norm() - 1.0).abs() < 1e-12);
+        assert!(points
+            .iter()
+            .all(|p| plane.signed_distance(p).abs() < 1e-12));
+        // the positive side makes positively oriented simplices with the points.
+        let q = Point::new_from_vec(&vec![0.1, 0.0, 0.3, 0.0]);
+        let mut vertices = points.clone();
+        vertices.push(q.clone());
+        let volume = Simplex::new(&vertices).signed_volume();
+        let (side, opposite) = if volume > 0.0 {
+            (Side::Positive, Side::Negative)
+        } else {
+            (Side::Negative, Side::Positive)
+        };
+        assert_eq!(plane.side(&q, 1e-9), side);
+        assert_eq!(plane.flipped().side(&q, 1e-9), opposite);
+        // the distance is the height of the simplex.
+        let base = Simplex::new(&vertices).facet_volumes()[4];
+        assert!((plane.signed_distance(&q).abs() - 4.0 * volume.abs() / base).abs() < 1e-12);
+        // in 2D, the normal points to the left.
+        let line = Hyperplane::from_points(&[
+            Point::new_from_vec(&vec![0.0, 0.0]),
+            Point::new_from_vec(&vec![2.0, 0.0]),
+        ])
+        .unwrap();
+        assert_eq!(line.normal().get_vector(), &vec![0.0, 1.0]);
+        assert_eq!(
+            line.side(&Point::new_from_vec(&vec![1.0, 0.0]), 1e-9),
+            Side::On
+        );
+        // in 3D
+        let corners: Vec<Point<f64>> = vec![
+            Point::new_from_vec(&vec![1.0, 0.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 1.0, 0.0]),
+            Point::new_from_vec(&vec![0.0, 0.0, 1.0]),
+        ];
+        let plane = Hyperplane::from_points(&corners).unwrap();
+        let origin = Point::new_from_vec(&vec![0.0, 0.0, 0.0]);
+        let mut vertices = corners.clone();
+        vertices.push(origin.clone());
+        assert!(Simplex::new(&vertices).signed_volume() > 0.0);
+        assert_eq!(plane.side(&origin, 1e-9), Side::Positive);
+        // in 1D, the hyperplane is a point.
+        let point = Hyperplane::from_points(&[Point::new_from_vec(&vec![3.0])]).unwrap();
+        assert_eq!(point.signed_distance(&Point::new_from_vec(&vec![1.0])), 2.0);
+        // degenerate inputs
+        assert!(Hyperplane::from_points(&[
+            Point::new_from_vec(&vec![0.0, 0.0, 0.0]),
+            Point::new_from_vec(&vec![1.0, 1.0, 1.0]),
+            Point::new_from_vec(&vec![2.0, 2.0, 2.0]),
+        ])
+        .is_none());
+        assert!(Hyperplane::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0).is_none());
+    }
+    #[test]
+    fn hyperplane_projection_and_intersection() {
+        use crate::hyperplane::Hyperplane;
+        let plane: Hyperplane<f64> = Hyperplane::from_point_normal(
+            &Point::new_from_vec(&vec![1.0, 2.0, 3.0]),
+            &Point::new_from_vec(&vec![1.0, -2.0, 2.0]),
+        )
+        .unwrap();
+        assert!((plane.offset() - 1.0).abs() < 1e-12);
+        let p = Point::new_from_vec(&vec![4.0, -1.0, 0.5]);
+        let projection = plane.project(&p);
+        let reflection = plane.reflect(&p);
+        assert!(plane.signed_distance(&projection).abs() < 1e-12);
+        assert!((plane.signed_distance(&reflection) + plane.signed_distance(&p)).abs() < 1e-12);
+        assert!(plane.reflect(&reflection).close(&p, 1e-12));
+        // the projection is the midpoint of the point and its reflection.
+        let midpoint = &(&p + &reflection) * &0.5;
+        assert!(midpoint.close(&projection, 1e-12));
+        let direction = Point::new_from_vec(&vec![0.0, 0.0, 1.0]);
+        let hit = plane.intersect_line(&p, &direction).unwrap();
+        assert!(plane.signed_distance(&hit).abs() < 1e-12);
+        assert!((plane.line_parameter(&p, &direction).unwrap() + 2.0).abs() < 1e-12);
+        // a line in a parallel direction
+        let parallel = Point::new_from_vec(&vec![2.0, 1.0, 0.0]);
+        assert!(plane.intersect_line(&p, &parallel).is_none());
+        assert!(plane.intersect_line(&projection, &parallel).is_none());
+        // segments
+        let hit = plane.intersect_segment(&p, &reflection).unwrap();
+        assert!(hit.close(&projection, 1e-12));
+        assert!(plane.intersect_segment(&p, &(&p + &direction)).is_none());
+    }
     //clone test
     #[test]
     fn clone() {
@@ -0,0 +1,515 @@
+//! Borrowed points, over values that are stored somewhere else.
+use crate::Point;
+use num::traits::Signed;
+use num::{Float, Zero};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, Sub, SubAssign};
+
+/// A read-only point over a borrowed slice of values. It supports the read-only operations of
+/// [`Point`] without copying the values; arithmetic returns a new owned [`Point`].
+///
+/// # Example
+/// ```
+/// use multi_dim_point::{Point, PointView};
+/// let buffer = vec![1, 2, 3, 4, 5, 6];
+/// let p1 = PointView::new(&buffer[0..3]);
+/// let p2 = PointView::new(&buffer[3..6]);
+/// assert_eq!(p2.get_value(1), &4);
+/// let sum: Point<i32> = &p1 + &p2;
+/// assert_eq!(sum.get_vector(), &vec![5, 7, 9]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PointView<'a, T> {
+    values: &'a [T],
+}
+
+/// A point over a mutably borrowed slice of values, for in-place changes.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::{Point, PointViewMut};
+/// let mut buffer = vec![1, 2, 3, 4];
+/// let other: Point<i32> = Point::new_from_vec(&vec![10, 10]);
+/// let mut p1 = PointViewMut::new(&mut buffer[2..4]);
+/// p1 += other.as_view();
+/// p1 *= 2;
+/// assert_eq!(buffer, vec![1, 2, 26, 28]);
+/// ```
+#[derive(Debug)]
+pub struct PointViewMut<'a, T> {
+    values: &'a mut [T],
+}
+
+fn check_valid_dim(dim: usize, size: usize) {
+    if dim < 1 {
+        panic!("dimenstion start from 1 ({} < 1)", dim);
+    }
+    if dim > size {
+        panic!("{} is bigger than {} (point max dimension)", dim, size);
+    }
+}
+
+impl<'a, T> PointView<'a, T>
+where
+    T: Clone,
+{
+    /// Creates a view over the values. The dimension of the point is the length of the slice.
+    pub fn new(values: &'a [T]) -> PointView<'a, T> {
+        PointView { values }
+    }
+
+    /// Return the values of the point.
+    pub fn get_vector(&self) -> &'a [T] {
+        self.values
+    }
+
+    /// Return a value in a specific dimension (see [`Point::get_value`]).
+    /// # Panic
+    /// This function will panic when the dimension index is smaller than 1, or bigger than the
+    /// point's dimension.
+    pub fn get_value(&self, dim_index: usize) -> &'a T {
+        check_valid_dim(dim_index, self.values.len());
+        &self.values[dim_index - 1] // dim start from 1, slice index from 0.
+    }
+
+    /// The function will return the number of dimensions of the point.
+    pub fn get_size(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Copy the values into an owned point.
+    pub fn to_point(&self) -> Point<T> {
+        Point::new_from_vec(&self.values.to_vec())
+    }
+
+    /// Apply a function on every pair of values in the same dimension (see
+    /// [`Point::apply_func`]).
+    /// # Panic
+    /// The function will panic when the points are without equal dimensions.
+    pub fn apply_func<S>(&self, other: &PointView<T>, f: &dyn Fn(&T, &T) -> S) -> Vec<S> {
+        crate::zip_values(self.values, other.values, f)
+    }
+
+    fn map<S>(&self, f: impl Fn(&T) -> S) -> Vec<S> {
+        self.values.iter().map(f).collect()
+    }
+
+    fn all_pairs(&self, other: &PointView<T>, f: impl Fn(&T, &T) -> bool) -> bool {
+        self.get_size() == other.get_size()
+            && self
+                .values
+                .iter()
+                .zip(other.values.iter())
+                .all(|(a, b)| f(a, b))
+    }
+}
+
+impl<'a, T> PointView<'a, T>
+where
+    T: Clone + Copy,
+{
+    /// Hadamard product (see [`Point::hadamard_mul`]).
+    pub fn hadamard_mul(&self, other: &PointView<T>) -> Point<T>
+    where
+        T: Mul<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a * *b))
+    }
+
+    /// Hadamard division (see [`Point::hadamard_div`]).
+    pub fn hadamard_div(&self, other: &PointView<T>) -> Point<T>
+    where
+        T: Div<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a / *b))
+    }
+
+    /// Hadamard remainder (see [`Point::hadamard_rem`]).
+    pub fn hadamard_rem(&self, other: &PointView<T>) -> Point<T>
+    where
+        T: Rem<Output = T>,
+    {
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a % *b))
+    }
+
+    /// Check if the points are close to each other, in each dimension, up to epsilon (see
+    /// [`Point::close`]).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::PointView;
+    /// let p1 = PointView::new(&[5, 10, 15]);
+    /// let p2 = PointView::new(&[7, 8, 14]);
+    /// assert!(p1.close(&p2, 3));
+    /// ```
+    pub fn close(&self, other: &PointView<T>, eps: T) -> bool
+    where
+        T: Signed + PartialOrd,
+    {
+        self.all_pairs(other, |a, b| (*a - *b).abs() <= eps)
+    }
+
+    /// Check if the points are close to each other, in each dimension, up to epsilon, for
+    /// unsigned types too (see [`Point::close_abs_diff`]).
+    pub fn close_abs_diff(&self, other: &PointView<T>, eps: T) -> bool
+    where
+        T: PartialOrd + Sub<Output = T>,
+    {
+        self.all_pairs(other, |a, b| {
+            let diff = if a > b { *a - *b } else { *b - *a };
+            diff <= eps
+        })
+    }
+}
+
+impl<'a, T> PointView<'a, T>
+where
+    T: Clone + Copy + Zero + Mul<Output = T>,
+{
+    /// Dot product of the points (see [`Point::dot`]).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::PointView;
+    /// let buffer = [1, 2, 3, 4, 5, 6];
+    /// let p1 = PointView::new(&buffer[0..3]);
+    /// let p2 = PointView::new(&buffer[3..6]);
+    /// assert_eq!(p1.dot(&p2), 32);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn dot(&self, other: &PointView<T>) -> T {
+        crate::dot_values(self.values, other.values)
+    }
+}
+
+impl<'a, T> PointView<'a, T>
+where
+    T: Float,
+{
+    /// Euclidean norm of the point (see [`Point::norm`]).
+    pub fn norm(&self) -> T {
+        crate::dot_values(self.values, self.values).sqrt()
+    }
+
+    /// Euclidean distance between the points (see [`Point::distance`]).
+    /// # Example
+    /// ```
+    /// use multi_dim_point::PointView;
+    /// let buffer = [1.0, 1.0, 4.0, 5.0];
+    /// let p1 = PointView::new(&buffer[0..2]);
+    /// let p2 = PointView::new(&buffer[2..4]);
+    /// assert_eq!(p1.distance(&p2), 5.0);
+    /// ```
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    pub fn distance(&self, other: &PointView<T>) -> T {
+        crate::distance_values(self.values, other.values)
+    }
+
+    /// Check if the points are close to each other, relative to the size of the values (see
+    /// [`Point::close_relative`]).
+    pub fn close_relative(&self, other: &PointView<T>, eps: T, max_relative: T) -> bool {
+        self.all_pairs(other, |a, b| {
+            crate::approx_eq::relative_eq(*a, *b, eps, max_relative)
+        })
+    }
+}
+
+impl<'a, T> PointView<'a, T>
+where
+    T: crate::Ulps,
+{
+    /// Check if the points are close to each other, by the number of representable floats
+    /// between the values (see [`Point::close_ulps`]).
+    pub fn close_ulps(&self, other: &PointView<T>, eps: T, max_ulps: u64) -> bool {
+        self.all_pairs(other, |a, b| {
+            crate::approx_eq::ulps_eq(*a, *b, eps, max_ulps)
+        })
+    }
+}
+
+impl<'a, T> From<PointView<'a, T>> for Point<T>
+where
+    T: Clone,
+{
+    fn from(view: PointView<'a, T>) -> Self {
+        view.to_point()
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Clone,
+{
+    /// Borrow the point as a [`PointView`].
+    pub fn as_view(&self) -> PointView<'_, T> {
+        PointView::new(self.get_vector())
+    }
+
+    /// Borrow the point as a [`PointViewMut`].
+    pub fn as_view_mut(&mut self) -> PointViewMut<'_, T> {
+        PointViewMut::new(&mut self.values)
+    }
+}
+
+impl<'a, 'b, T> Add<&PointView<'b, T>> for &PointView<'a, T>
+where
+    T: Clone + Copy + Add<Output = T>,
+{
+    type Output = Point<T>;
+    /// \+ operator. Adding values in each dimension into a new point.
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    fn add(self, other: &PointView<'b, T>) -> Point<T> {
+        if self.get_size() != other.get_size() {
+            panic!("dimensions are not equal, can't add");
+        }
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a + *b))
+    }
+}
+
+impl<'a, 'b, T> Sub<&PointView<'b, T>> for &PointView<'a, T>
+where
+    T: Clone + Copy + Sub<Output = T>,
+{
+    type Output = Point<T>;
+    /// \- operator. Subtraction values in each dimension into a new point.
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    fn sub(self, other: &PointView<'b, T>) -> Point<T> {
+        if self.get_size() != other.get_size() {
+            panic!("dimensions are not equal, can't sub");
+        }
+        Point::new_from_vec(&self.apply_func(other, &|a, b| *a - *b))
+    }
+}
+
+impl<'a, T, S> Mul<&S> for &PointView<'a, T>
+where
+    T: Clone + Copy + Mul<S, Output = T>,
+    S: Copy,
+{
+    type Output = Point<T>;
+    /// \* operator. Multiply each value into a new point.
+    fn mul(self, scalar: &S) -> Point<T> {
+        Point::new_from_vec(&self.map(|a| *a * *scalar))
+    }
+}
+
+impl<'a, T, S> Div<&S> for &PointView<'a, T>
+where
+    T: Clone + Copy + Div<S, Output = T>,
+    S: Copy,
+{
+    type Output = Point<T>;
+    /// / operator. Divide each value into a new point.
+    fn div(self, scalar: &S) -> Point<T> {
+        Point::new_from_vec(&self.map(|a| *a / *scalar))
+    }
+}
+
+impl<'a, 'b, T> Rem<&PointView<'b, T>> for &PointView<'a, T>
+where
+    T: Clone + Copy + Rem<Output = T>,
+{
+    type Output = Point<T>;
+    /// % operator. Remainder of the values in each dimension (see [`PointView::hadamard_rem`]).
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    fn rem(self, other: &PointView<'b, T>) -> Point<T> {
+        self.hadamard_rem(other)
+    }
+}
+
+/// Implement the operators that combine a view with a primitive scalar (like the ones of
+/// [`Point`]).
+macro_rules! impl_view_scalar_ops {
+    ($($t:ty)*) => ($(
+        impl<'a> Mul<&PointView<'a, $t>> for &$t {
+            type Output = Point<$t>;
+            /// \* operator, with the scalar on the left. Multiply each value into a new point.
+            fn mul(self, view: &PointView<'a, $t>) -> Point<$t> {
+                view * self
+            }
+        }
+
+        impl<'a> Mul<PointView<'a, $t>> for $t {
+            type Output = Point<$t>;
+            /// \* operator, with the scalar on the left. Multiply each value into a new point.
+            fn mul(self, view: PointView<'a, $t>) -> Point<$t> {
+                &view * &self
+            }
+        }
+
+        impl<'a> Add<&$t> for &PointView<'a, $t> {
+            type Output = Point<$t>;
+            /// \+ operator. Add the scalar to each value into a new point.
+            fn add(self, scalar: &$t) -> Point<$t> {
+                Point::new_from_vec(&self.map(|a| *a + *scalar))
+            }
+        }
+
+        impl<'a> Sub<&$t> for &PointView<'a, $t> {
+            type Output = Point<$t>;
+            /// \- operator. Subtract the scalar from each value into a new point.
+            fn sub(self, scalar: &$t) -> Point<$t> {
+                Point::new_from_vec(&self.map(|a| *a - *scalar))
+            }
+        }
+
+        impl<'a> AddAssign<$t> for PointViewMut<'a, $t> {
+            /// += operator. Add the scalar to each value in place.
+            fn add_assign(&mut self, scalar: $t) {
+                self.values.iter_mut().for_each(|a| *a += scalar);
+            }
+        }
+
+        impl<'a> SubAssign<$t> for PointViewMut<'a, $t> {
+            /// -= operator. Subtract the scalar from each value in place.
+            fn sub_assign(&mut self, scalar: $t) {
+                self.values.iter_mut().for_each(|a| *a -= scalar);
+            }
+        }
+    )*)
+}
+
+impl_view_scalar_ops! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64 }
+
+impl<'a, 'b, T> PartialEq<PointView<'b, T>> for PointView<'a, T>
+where
+    T: PartialEq + Clone,
+{
+    fn eq(&self, other: &PointView<'b, T>) -> bool {
+        self.all_pairs(other, |a, b| a == b)
+    }
+}
+
+impl<'a, T> PartialEq<Point<T>> for PointView<'a, T>
+where
+    T: PartialEq + Clone,
+{
+    fn eq(&self, other: &Point<T>) -> bool {
+        *self == other.as_view()
+    }
+}
+
+impl<'a, T> PartialEq<PointView<'a, T>> for Point<T>
+where
+    T: PartialEq + Clone,
+{
+    fn eq(&self, other: &PointView<'a, T>) -> bool {
+        self.as_view() == *other
+    }
+}
+
+impl<'a, T> fmt::Display for PointView<'a, T>
+where
+    T: fmt::Display + Clone,
+{
+    /// Format the point like [`Point`] does.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::fmt_values(self.values, f)
+    }
+}
+
+impl<'a, T> PointViewMut<'a, T>
+where
+    T: Clone,
+{
+    /// Creates a mutable view over the values. The dimension of the point is the length of the
+    /// slice.
+    pub fn new(values: &'a mut [T]) -> PointViewMut<'a, T> {
+        PointViewMut { values }
+    }
+
+    /// Reborrow as a read-only [`PointView`], for all the read-only operations.
+    pub fn as_view(&self) -> PointView<'_, T> {
+        PointView::new(self.values)
+    }
+
+    /// Return the values of the point.
+    pub fn get_vector(&self) -> &[T] {
+        self.values
+    }
+
+    /// Return a value in a specific dimension (see [`Point::get_value`]).
+    /// # Panic
+    /// This function will panic when the dimension is invalid.
+    pub fn get_value(&self, dim_index: usize) -> &T {
+        check_valid_dim(dim_index, self.values.len());
+        &self.values[dim_index - 1]
+    }
+
+    /// Change the value of the point in a specific dimension (see [`Point::set_value`]).
+    /// # Panic
+    /// This function will panic when the dimension is invalid.
+    pub fn set_value(&mut self, dim: usize, new_val: &T) {
+        check_valid_dim(dim, self.values.len());
+        self.values[dim - 1] = new_val.clone(); // dim start from 1, index from 0.
+    }
+
+    /// The function will return the number of dimensions of the point.
+    pub fn get_size(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Copy the values into an owned point.
+    pub fn to_point(&self) -> Point<T> {
+        self.as_view().to_point()
+    }
+
+    fn zip_assign(&mut self, other: PointView<T>, f: impl Fn(&mut T, &T)) {
+        if self.get_size() != other.get_size() {
+            panic!("dimensions are not equal");
+        }
+        self.values
+            .iter_mut()
+            .zip(other.get_vector().iter())
+            .for_each(|(a, b)| f(a, b));
+    }
+}
+
+impl<'a, 'b, T> AddAssign<PointView<'b, T>> for PointViewMut<'a, T>
+where
+    T: Clone + Copy + Add<Output = T>,
+{
+    /// += operator. Add the values of the other point in place.
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    fn add_assign(&mut self, other: PointView<'b, T>) {
+        self.zip_assign(other, |a, b| *a = *a + *b);
+    }
+}
+
+impl<'a, 'b, T> SubAssign<PointView<'b, T>> for PointViewMut<'a, T>
+where
+    T: Clone + Copy + Sub<Output = T>,
+{
+    /// -= operator. Subtract the values of the other point in place.
+    /// # Panic
+    /// This function will panic if the dimensions of the points are not equal.
+    fn sub_assign(&mut self, other: PointView<'b, T>) {
+        self.zip_assign(other, |a, b| *a = *a - *b);
+    }
+}
+
+impl<'a, T, S> MulAssign<S> for PointViewMut<'a, T>
+where
+    T: Clone + Copy + Mul<S, Output = T>,
+    S: Copy,
+{
+    /// *= operator. Multiply each value in place.
+    fn mul_assign(&mut self, scalar: S) {
+        self.values.iter_mut().for_each(|a| *a = *a * scalar);
+    }
+}
+
+impl<'a, T, S> DivAssign<S> for PointViewMut<'a, T>
+where
+    T: Clone + Copy + Div<S, Output = T>,
+    S: Copy,
+{
+    /// /= operator. Divide each value in place.
+    fn div_assign(&mut self, scalar: S) {
+        self.values.iter_mut().for_each(|a| *a = *a / scalar);
+    }
+}
@@ -0,0 +1,570 @@
+//! Voronoi diagrams of 2D and 3D points, clipped to a bounding box.
+use crate::delaunay::Delaunay;
+use crate::predicates::orient;
+use crate::{dot_values, zip_values, Point};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// A vertex of a polygon being clipped, with the label of the edge starting at it.
+type LabelledVertex = (Vec<f64>, Option<usize>);
+
+/// A face of a polyhedron being clipped: its vertices (counterclockwise seen from outside), and
+/// the neighbour whose bisector it is on (`None` for the bounding box).
+struct Face {
+    vertices: Vec<Vec<f64>>,
+    label: Option<usize>,
+}
+
+fn cross(a: &[f64], b: &[f64]) -> Vec<f64> {
+    vec![
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// The point where the segment crosses the plane, from the signed distances of its ends.
+fn crossing(a: &[f64], b: &[f64], side_a: f64, side_b: f64) -> Vec<f64> {
+    let t = side_a / (side_a - side_b);
+    a.iter().zip(b).map(|(x, y)| x + (y - x) * t).collect()
+}
+
+/// A half-space `normal . x <= offset`, with the label of the neighbour it comes from.
+struct HalfSpace {
+    normal: Vec<f64>,
+    offset: f64,
+    label: usize,
+    tolerance: f64,
+}
+
+impl HalfSpace {
+    /// The signed distance (scaled by the norm of the normal), with 0 in the tolerance.
+    fn side(&self, point: &[f64]) -> f64 {
+        let side = dot_values(&self.normal, point) - self.offset;
+        if side.abs() <= self.tolerance {
+            0.0
+        } else {
+            side
+        }
+    }
+
+    /// Clip a polygon with labelled edges (Sutherland-Hodgman).
+    fn clip_polygon(&self, polygon: &[LabelledVertex]) -> Vec<LabelledVertex> {
+        let mut clipped = Vec::new();
+        for (i, (a, label)) in polygon.iter().enumerate() {
+            let b = &polygon[(i + 1) % polygon.len()].0;
+            let (side_a, side_b) = (self.side(a), self.side(b));
+            if side_a <= 0.0 {
+                clipped.push((a.clone(), *label));
+                if side_b > 0.0 && side_a < 0.0 {
+                    clipped.push((crossing(a, b, side_a, side_b), Some(self.label)));
+                } else if side_b > 0.0 {
+                    // the edge leaves from a point on the plane.
+                    clipped.last_mut().unwrap().1 = Some(self.label);
+                }
+            } else if side_b < 0.0 {
+                clipped.push((crossing(a, b, side_a, side_b), *label));
+            }
+        }
+        clipped
+    }
+
+    /// Clip a convex polyhedron, closing it with a face on the plane.
+    fn clip_polyhedron(&self, faces: Vec<Face>) -> Vec<Face> {
+        let mut clipped = Vec::new();
+        let mut cap: Vec<Vec<f64>> = Vec::new();
+        for face in faces {
+            let polygon: Vec<LabelledVertex> =
+                face.vertices.into_iter().map(|v| (v, None)).collect();
+            let vertices: Vec<Vec<f64>> = self
+                .clip_polygon(&polygon)
+                .into_iter()
+                .map(|(v, _)| v)
+                .collect();
+            let on_plane = vertices.iter().filter(|v| self.side(v) == 0.0).count();
+            cap.extend(vertices.iter().filter(|v| self.side(v) == 0.0).cloned());
+            // a face on the plane is replaced by the cap.
+            if vertices.len() >= 3 && on_plane < vertices.len() {
+                clipped.push(Face {
+                    vertices,
+                    label: face.label,
+                });
+            }
+        }
+        if clipped.is_empty() {
+            return clipped;
+        }
+        let cap = dedup_points(cap, self.tolerance);
+        if cap.len() >= 3 {
+            // order the points counterclockwise around the normal.
+            let center: Vec<f64> = (0..3)
+                .map(|i| cap.iter().map(|p| p[i]).sum::<f64>() / cap.len() as f64)
+                .collect();
+            let u = zip_values(&cap[0], &center, |x, y| x - y);
+            let v = cross(&self.normal, &u);
+            let mut ordered: Vec<(f64, Vec<f64>)> = cap
+                .into_iter()
+                .map(|p| {
+                    let d = zip_values(&p, &center, |x, y| x - y);
+                    (dot_values(&d, &v).atan2(dot_values(&d, &u)), p)
+                })
+                .collect();
+            ordered.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            let vertices: Vec<Vec<f64>> = ordered.into_iter().map(|(_, p)| p).collect();
+            // a cap on an edge or a vertex of the polyhedron has no area.
+            let doubled_area = (1..vertices.len() - 1)
+                .map(|i| {
+                    let (a, b) = (
+                        zip_values(&vertices[i], &vertices[0], |x, y| x - y),
+                        zip_values(&vertices[i + 1], &vertices[0], |x, y| x - y),
+                    );
+                    dot_values(&cross(&a, &b), &self.normal)
+                })
+                .sum::<f64>();
+            if doubled_area > self.tolerance * self.tolerance {
+                clipped.push(Face {
+                    vertices,
+                    label: Some(self.label),
+                });
+            }
+        }
+        clipped
+    }
+}
+
+/// The points without the points closer than the tolerance to a previous point.
+fn dedup_points(points: Vec<Vec<f64>>, tolerance: f64) -> Vec<Vec<f64>> {
+    let mut unique: Vec<Vec<f64>> = Vec::new();
+    for p in points {
+        if !unique
+            .iter()
+            .any(|q| q.iter().zip(&p).all(|(a, b)| (a - b).abs() <= tolerance))
+        {
+            unique.push(p);
+        }
+    }
+    unique
+}
+
+/// The neighbours of the sites of a triangulation without simplices: the nearest distinct site
+/// on each side if the sites are on a line, or else (coplanar sites in 3D) every distinct site.
+/// Equal sites have the same neighbours, and only the first of them is a neighbour.
+fn degenerate_neighbours(points: &[Point<f64>]) -> Vec<Vec<usize>> {
+    let coordinates = |i: usize| points[i].get_vector().as_slice();
+    let distinct = |a: usize, b: usize| coordinates(a) != coordinates(b);
+    let second = match (1..points.len()).find(|i| distinct(0, *i)) {
+        Some(second) => second,
+        None => return vec![Vec::new(); points.len()],
+    };
+    let dim = points[0].get_size();
+    // on the line of the first two sites, in every projection to 2 axes (exactly).
+    let on_line = (0..points.len()).all(|i| {
+        (0..dim).all(|a| {
+            (a + 1..dim).all(|b| {
+                let project = |p: usize| [coordinates(p)[a], coordinates(p)[b]];
+                orient(&[&project(0), &project(second), &project(i)]) == Ordering::Equal
+            })
+        })
+    });
+    if !on_line {
+        return (0..points.len())
+            .map(|site| (0..points.len()).filter(|o| distinct(site, *o)).collect())
+            .collect();
+    }
+    // sort along the line (equal sites are together, in their order), and group equal sites.
+    let direction = zip_values(coordinates(second), coordinates(0), |x, y| x - y);
+    let position = |i: usize| {
+        dot_values(
+            &zip_values(coordinates(i), coordinates(0), |x, y| x - y),
+            &direction,
+        )
+    };
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| {
+        position(*a)
+            .partial_cmp(&position(*b))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                coordinates(*a)
+                    .partial_cmp(coordinates(*b))
+                    .unwrap_or(Ordering::Equal)
+            })
+    });
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for i in order {
+        match groups.last_mut() {
+            Some(group) if !distinct(group[0], i) => group.push(i),
+            _ => groups.push(vec![i]),
+        }
+    }
+    let mut neighbours = vec![Vec::new(); points.len()];
+    for (g, group) in groups.iter().enumerate() {
+        let adjacent: Vec<usize> = [g.checked_sub(1), Some(g + 1)]
+            .iter()
+            .flatten()
+            .filter_map(|k| groups.get(*k))
+            .map(|other| other[0])
+            .collect();
+        for site in group {
+            neighbours[*site] = adjacent.clone();
+        }
+    }
+    neighbours
+}
+
+/// The cell of one point (the site) in a Voronoi diagram: the part of the bounding box that is
+/// closer to the site than to any other point.
+#[derive(Debug, Clone)]
+pub struct VoronoiCell {
+    site: usize,
+    vertices: Vec<Point<f64>>,
+    faces: Vec<Vec<usize>>,
+    face_neighbours: Vec<Option<usize>>,
+    measure: f64,
+}
+
+impl VoronoiCell {
+    /// The index of the site in the points.
+    pub fn site(&self) -> usize {
+        self.site
+    }
+
+    /// The vertices of the cell (in 2D, counterclockwise).
+    pub fn vertices(&self) -> &[Point<f64>] {
+        &self.vertices
+    }
+
+    /// The faces of the cell (edges in 2D), as indices in the vertices. In 3D, each face is
+    /// counterclockwise seen from outside the cell.
+    pub fn faces(&self) -> &[Vec<usize>] {
+        &self.faces
+    }
+
+    /// For each face, the site of the cell on its other side, or `None` for the bounding box.
+    pub fn face_neighbours(&self) -> &[Option<usize>] {
+        &self.face_neighbours
+    }
+
+    /// The sites of the adjacent cells (the cells that share a face with this cell).
+    pub fn neighbours(&self) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = self.face_neighbours.iter().flatten().copied().collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+
+    /// Returns `true` if the cell is empty (the site is duplicated, or far outside the box).
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// The area of a 2D cell, or the volume of a 3D cell.
+    pub fn measure(&self) -> f64 {
+        self.measure
+    }
+}
+
+/// A Voronoi diagram of 2D or 3D points, clipped to an axis-aligned bounding box.
+///
+/// Each cell is the bounding box clipped by the bisectors between its site and the site's
+/// neighbours in the Delaunay triangulation.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::voronoi::Voronoi;
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![1.0, 1.0]),
+///     Point::new_from_vec(&vec![3.0, 1.0]),
+///     Point::new_from_vec(&vec![1.0, 3.0]),
+///     Point::new_from_vec(&vec![3.0, 3.0]),
+/// ];
+/// let min = Point::new_from_vec(&vec![0.0, 0.0]);
+/// let max = Point::new_from_vec(&vec![4.0, 4.0]);
+/// let voronoi = Voronoi::new(&points, &min, &max);
+/// assert!((voronoi.cells()[0].measure() - 4.0).abs() < 1e-12);
+/// assert_eq!(voronoi.cells()[0].neighbours(), vec![1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Voronoi {
+    cells: Vec<VoronoiCell>,
+}
+
+impl Voronoi {
+    /// Creates the Voronoi diagram of the points, clipped to the box between `min` and `max`.
+    /// # Panic
+    /// This function will panic if the points are not 2 or 3 dimensional, if the dimensions of
+    /// the points and the box are not equal, or if `min` is not lower than `max`.
+    pub fn new(points: &[Point<f64>], min: &Point<f64>, max: &Point<f64>) -> Voronoi {
+        Voronoi::from_delaunay(&Delaunay::new(points), min, max)
+    }
+
+    /// Creates the Voronoi diagram of the points of a Delaunay triangulation, clipped to the box
+    /// between `min` and `max`.
+    /// # Panic
+    /// This function will panic if the dimensions of the points and the box are not equal, or
+    /// if `min` is not lower than `max`.
+    pub fn from_delaunay(triangulation: &Delaunay, min: &Point<f64>, max: &Point<f64>) -> Voronoi {
+        let points = triangulation.points();
+        let dim = min.get_size();
+        if max.get_size() != dim || points.iter().any(|p| p.get_size() != dim) {
+            panic!("dimensions are not equal");
+        }
+        if dim != 2 && dim != 3 {
+            panic!("Voronoi diagrams support 2 and 3 dimensions, found {}", dim);
+        }
+        if min
+            .get_vector()
+            .iter()
+            .zip(max.get_vector())
+            .any(|(a, b)| a >= b)
+        {
+            panic!("the minimum corner of the box must be lower than the maximum corner");
+        }
+        let size = min
+            .get_vector()
+            .iter()
+            .zip(max.get_vector())
+            .map(|(a, b)| b - a)
+            .fold(0.0, f64::max);
+        let tolerance = 1e-12 * size.max(1.0);
+        // the neighbours of each site in the triangulation.
+        let mut neighbours: Vec<Vec<usize>> = if triangulation.simplices().is_empty() {
+            degenerate_neighbours(points)
+        } else {
+            vec![Vec::new(); points.len()]
+        };
+        for simplex in triangulation.simplices() {
+            for a in simplex {
+                neighbours[*a].extend(simplex.iter().filter(|b| *b != a));
+            }
+        }
+        for n in neighbours.iter_mut() {
+            n.sort_unstable();
+            n.dedup();
+        }
+        // the sites equal to an earlier site, keyed on the bits of the values (`+ 0.0` turns -0.0
+        // into 0.0).
+        let mut seen = HashSet::new();
+        let duplicates: Vec<bool> = points
+            .iter()
+            .map(|p| {
+                let key: Vec<u64> = p.get_vector().iter().map(|v| (v + 0.0).to_bits()).collect();
+                !seen.insert(key)
+            })
+            .collect();
+        let cells = (0..points.len())
+            .map(|site| {
+                let duplicate_of_earlier = duplicates[site];
+                let half_spaces: Vec<HalfSpace> = if duplicate_of_earlier {
+                    Vec::new()
+                } else {
+                    neighbours[site]
+                        .iter()
+                        .map(|other| {
+                            let (p, q) = (points[site].get_vector(), points[*other].get_vector());
+                            let normal = zip_values(q, p, |x, y| x - y);
+                            let scale = size + dot_values(q, q).max(dot_values(p, p)).sqrt();
+                            HalfSpace {
+                                tolerance: 1e-12 * dot_values(&normal, &normal).sqrt() * scale,
+                                offset: (dot_values(q, q) - dot_values(p, p)) / 2.0,
+                                normal,
+                                label: *other,
+                            }
+                        })
+                        .collect()
+                };
+                if dim == 2 {
+                    cell_2d(
+                        site,
+                        min,
+                        max,
+                        &half_spaces,
+                        duplicate_of_earlier,
+                        tolerance,
+                    )
+                } else {
+                    cell_3d(
+                        site,
+                        min,
+                        max,
+                        &half_spaces,
+                        duplicate_of_earlier,
+                        tolerance,
+                    )
+                }
+            })
+            .collect();
+        Voronoi { cells }
+    }
+
+    /// The cells, in the order of their sites.
+    pub fn cells(&self) -> &[VoronoiCell] {
+        &self.cells
+    }
+}
+
+fn empty_cell(site: usize) -> VoronoiCell {
+    VoronoiCell {
+        site,
+        vertices: Vec::new(),
+        faces: Vec::new(),
+        face_neighbours: Vec::new(),
+        measure: 0.0,
+    }
+}
+
+fn cell_2d(
+    site: usize,
+    min: &Point<f64>,
+    max: &Point<f64>,
+    half_spaces: &[HalfSpace],
+    empty: bool,
+    tolerance: f64,
+) -> VoronoiCell {
+    if empty {
+        return empty_cell(site);
+    }
+    let (lo, hi) = (min.get_vector(), max.get_vector());
+    let mut polygon: Vec<LabelledVertex> = vec![
+        (vec![lo[0], lo[1]], None),
+        (vec![hi[0], lo[1]], None),
+        (vec![hi[0], hi[1]], None),
+        (vec![lo[0], hi[1]], None),
+    ];
+    for h in half_spaces {
+        polygon = h.clip_polygon(&polygon);
+        if polygon.is_empty() {
+            return empty_cell(site);
+        }
+    }
+    // drop the edges shorter than the tolerance.
+    let mut vertices: Vec<LabelledVertex> = Vec::new();
+    for (p, label) in polygon {
+        match vertices.last_mut() {
+            Some((q, last_label)) if q.iter().zip(&p).all(|(a, b)| (a - b).abs() <= tolerance) => {
+                *last_label = label;
+            }
+            _ => vertices.push((p, label)),
+        }
+    }
+    while vertices.len() > 1
+        && vertices[0]
+            .0
+            .iter()
+            .zip(&vertices[vertices.len() - 1].0)
+            .all(|(a, b)| (a - b).abs() <= tolerance)
+    {
+        vertices.pop();
+    }
+    if vertices.len() < 3 {
+        return empty_cell(site);
+    }
+    let n = vertices.len();
+    let area = (0..n)
+        .map(|i| {
+            let (a, b) = (&vertices[i].0, &vertices[(i + 1) % n].0);
+            a[0] * b[1] - a[1] * b[0]
+        })
+        .sum::<f64>()
+        / 2.0;
+    VoronoiCell {
+        site,
+        faces: (0..n).map(|i| vec![i, (i + 1) % n]).collect(),
+        face_neighbours: vertices.iter().map(|(_, label)| *label).collect(),
+        vertices: vertices
+            .into_iter()
+            .map(|(p, _)| Point::new_from_vec(&p))
+            .collect(),
+        measure: area,
+    }
+}
+
+fn cell_3d(
+    site: usize,
+    min: &Point<f64>,
+    max: &Point<f64>,
+    half_spaces: &[HalfSpace],
+    empty: bool,
+    tolerance: f64,
+) -> VoronoiCell {
+    if empty {
+        return empty_cell(site);
+    }
+    let (lo, hi) = (min.get_vector(), max.get_vector());
+    let corner = |i: usize| {
+        vec![
+            if i & 1 == 0 { lo[0] } else { hi[0] },
+            if i & 2 == 0 { lo[1] } else { hi[1] },
+            if i & 4 == 0 { lo[2] } else { hi[2] },
+        ]
+    };
+    // the faces of the box, counterclockwise seen from outside.
+    let box_faces = [
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+    ];
+    let mut faces: Vec<Face> = box_faces
+        .iter()
+        .map(|f| Face {
+            vertices: f.iter().map(|i| corner(*i)).collect(),
+            label: None,
+        })
+        .collect();
+    for h in half_spaces {
+        faces = h.clip_polyhedron(faces);
+        if faces.is_empty() {
+            return empty_cell(site);
+        }
+    }
+    // index the vertices, dropping faces that became too small.
+    let mut vertices: Vec<Vec<f64>> = Vec::new();
+    let mut indexed_faces = Vec::new();
+    let mut face_neighbours = Vec::new();
+    let mut volume = 0.0;
+    for face in &faces {
+        let mut indices: Vec<usize> = Vec::new();
+        for p in &face.vertices {
+            let index = match vertices
+                .iter()
+                .position(|q| q.iter().zip(p).all(|(a, b)| (a - b).abs() <= tolerance))
+            {
+                Some(index) => index,
+                None => {
+                    vertices.push(p.clone());
+                    vertices.len() - 1
+                }
+            };
+            if indices.last() != Some(&index) && indices.first() != Some(&index) {
+                indices.push(index);
+            }
+        }
+        if indices.len() < 3 {
+            continue;
+        }
+        for i in 1..indices.len() - 1 {
+            let (a, b, c) = (
+                &vertices[indices[0]],
+                &vertices[indices[i]],
+                &vertices[indices[i + 1]],
+            );
+            volume += dot_values(a, &cross(b, c)) / 6.0;
+        }
+        indexed_faces.push(indices);
+        face_neighbours.push(face.label);
+    }
+    if indexed_faces.len() < 4 {
+        return empty_cell(site);
+    }
+    VoronoiCell {
+        site,
+        vertices: vertices.iter().map(Point::new_from_vec).collect(),
+        faces: indexed_faces,
+        face_neighbours,
+        measure: volume,
+    }
+}
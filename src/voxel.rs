@@ -0,0 +1,337 @@
+//! Voxel grids: quantization of float points to integer cells, a hashed map of cells, and
+//! downsampling.
+use crate::Point;
+use std::collections::HashMap;
+
+/// A regular grid of cubic cells, with a cell corner in the origin.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::voxel::VoxelGrid;
+/// use multi_dim_point::Point;
+/// let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 0.5);
+/// let cell = grid.cell_of(&Point::new_from_vec(&vec![1.2, -0.2]));
+/// assert_eq!(cell.get_vector(), &vec![2, -1]);
+/// assert_eq!(grid.cell_center(&cell).get_vector(), &vec![1.25, -0.25]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    origin: Point<f64>,
+    cell_size: f64,
+}
+
+impl VoxelGrid {
+    /// Creates a grid with the origin and the size of the cells.
+    /// # Panic
+    /// This function will panic if the cell size is not positive.
+    pub fn new(origin: &Point<f64>, cell_size: f64) -> VoxelGrid {
+        if cell_size <= 0.0 || cell_size.is_nan() {
+            panic!("the cell size must be positive, found {}", cell_size);
+        }
+        VoxelGrid {
+            origin: origin.clone(),
+            cell_size,
+        }
+    }
+
+    /// The origin of the grid.
+    pub fn origin(&self) -> &Point<f64> {
+        &self.origin
+    }
+
+    /// The size of the cells.
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// The dimension of the grid.
+    pub fn get_size(&self) -> usize {
+        self.origin.get_size()
+    }
+
+    fn check_dimension(&self, dim: usize) {
+        if dim != self.get_size() {
+            panic!("dimensions are not equal");
+        }
+    }
+
+    /// The integer coordinates of the cell that contains the point.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the grid are not equal, or if
+    /// the point has a NaN value or is too far from the origin for `i64` cell coordinates.
+    pub fn cell_of(&self, point: &Point<f64>) -> Point<i64> {
+        self.check_dimension(point.get_size());
+        let values = point
+            .get_vector()
+            .iter()
+            .zip(self.origin.get_vector())
+            .map(|(p, o)| {
+                let cell = ((p - o) / self.cell_size).floor();
+                // also false for NaN.
+                if !(cell >= i64::MIN as f64 && cell < i64::MAX as f64) {
+                    panic!("the point {} has no cell in the grid", point);
+                }
+                cell as i64
+            })
+            .collect();
+        Point::new_from_vec(&values)
+    }
+
+    /// The corner of the cell with the lowest values.
+    /// # Panic
+    /// This function will panic if the dimensions of the cell and the grid are not equal.
+    pub fn cell_min(&self, cell: &Point<i64>) -> Point<f64> {
+        self.cell_point(cell, 0.0)
+    }
+
+    /// The center of the cell.
+    /// # Panic
+    /// This function will panic if the dimensions of the cell and the grid are not equal.
+    pub fn cell_center(&self, cell: &Point<i64>) -> Point<f64> {
+        self.cell_point(cell, 0.5)
+    }
+
+    fn cell_point(&self, cell: &Point<i64>, offset: f64) -> Point<f64> {
+        self.check_dimension(cell.get_size());
+        let values = cell
+            .get_vector()
+            .iter()
+            .zip(self.origin.get_vector())
+            .map(|(c, o)| o + (*c as f64 + offset) * self.cell_size)
+            .collect();
+        Point::new_from_vec(&values)
+    }
+}
+
+/// The cells around a cell, up to `reach` cells away in every dimension (including the cell).
+/// Cells beyond the range of `i64` are skipped.
+fn neighbour_cells(cell: &Point<i64>, reach: i64) -> Vec<Point<i64>> {
+    let center = cell.get_vector();
+    let mut offset = vec![-reach; center.len()];
+    let mut cells = Vec::new();
+    loop {
+        let values: Option<Vec<i64>> = center
+            .iter()
+            .zip(&offset)
+            .map(|(c, o)| c.checked_add(*o))
+            .collect();
+        if let Some(values) = values {
+            cells.push(Point::new_from_vec(&values));
+        }
+        // advance the offsets like an odometer.
+        let mut i = 0;
+        while i < offset.len() && offset[i] == reach {
+            offset[i] = -reach;
+            i += 1;
+        }
+        if i == offset.len() {
+            return cells;
+        }
+        offset[i] += 1;
+    }
+}
+
+/// A hash map from the cells of a voxel grid to the values inserted in them, for constant time
+/// lookup of the values near a point.
+///
+/// # Example
+/// ```
+/// use multi_dim_point::voxel::{CellMap, VoxelGrid};
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.1, 0.1]),
+///     Point::new_from_vec(&vec![0.9, 0.2]),
+///     Point::new_from_vec(&vec![5.0, 5.0]),
+/// ];
+/// let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0);
+/// let map = CellMap::from_points(grid, &points);
+/// let mut near = map.within_distance(&points, &Point::new_from_vec(&vec![0.5, 0.5]), 1.0);
+/// near.sort_unstable();
+/// assert_eq!(near, vec![0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CellMap<V> {
+    grid: VoxelGrid,
+    cells: HashMap<Point<i64>, Vec<V>>,
+    len: usize,
+}
+
+impl<V> CellMap<V> {
+    /// Creates an empty map over the grid.
+    pub fn new(grid: VoxelGrid) -> CellMap<V> {
+        CellMap {
+            grid,
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// The grid of the map.
+    pub fn grid(&self) -> &VoxelGrid {
+        &self.grid
+    }
+
+    /// The number of values in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a value in the cell that contains the point.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the grid are not equal.
+    pub fn insert(&mut self, point: &Point<f64>, value: V) {
+        let cell = self.grid.cell_of(point);
+        self.cells.entry(cell).or_default().push(value);
+        self.len += 1;
+    }
+
+    /// The values in the cell.
+    pub fn get(&self, cell: &Point<i64>) -> &[V] {
+        self.cells.get(cell).map_or(&[], |values| values.as_slice())
+    }
+
+    /// The values in the cell that contains the point, and in the cells up to `reach` cells away
+    /// from it in every dimension.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the grid are not equal.
+    pub fn neighbours(&self, point: &Point<f64>, reach: usize) -> Vec<&V> {
+        let cell = self.grid.cell_of(point);
+        let count = reach
+            .checked_mul(2)
+            .and_then(|r| r.checked_add(1))
+            .and_then(|side| side.checked_pow(cell.get_size() as u32));
+        match count {
+            Some(count) if count <= self.cells.len() => neighbour_cells(&cell, reach as i64)
+                .iter()
+                .flat_map(|c| self.get(c))
+                .collect(),
+            // there are fewer cells with values than cells around the point.
+            _ => self
+                .cells
+                .iter()
+                .filter(|(c, _)| {
+                    c.get_vector()
+                        .iter()
+                        .zip(cell.get_vector())
+                        .all(|(a, b)| a.abs_diff(*b) <= reach as u64)
+                })
+                .flat_map(|(_, values)| values)
+                .collect(),
+        }
+    }
+
+    /// The cells that have values, with their values.
+    pub fn cells(&self) -> impl Iterator<Item = (&Point<i64>, &[V])> {
+        self.cells
+            .iter()
+            .map(|(cell, values)| (cell, values.as_slice()))
+    }
+}
+
+impl CellMap<usize> {
+    /// Creates a map of the indices of the points.
+    pub fn from_points(grid: VoxelGrid, points: &[Point<f64>]) -> CellMap<usize> {
+        let mut map = CellMap::new(grid);
+        for (i, p) in points.iter().enumerate() {
+            map.insert(p, i);
+        }
+        map
+    }
+
+    /// The indices of the points (the points the map was built from) that are up to `distance`
+    /// from the point.
+    /// # Panic
+    /// This function will panic if the dimensions of the point and the grid are not equal, or if
+    /// the distance is negative or not finite.
+    pub fn within_distance(
+        &self,
+        points: &[Point<f64>],
+        point: &Point<f64>,
+        distance: f64,
+    ) -> Vec<usize> {
+        if !distance.is_finite() || distance < 0.0 {
+            panic!(
+                "the distance must be finite and not negative, found {}",
+                distance
+            );
+        }
+        // saturates for huge distances, which `neighbours` handles.
+        let reach = (distance / self.grid.cell_size).ceil() as usize;
+        self.neighbours(point, reach)
+            .into_iter()
+            .copied()
+            .filter(|i| points[*i].distance(point) <= distance)
+            .collect()
+    }
+}
+
+/// The point that represents each cell when downsampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Representative {
+    /// The centroid of the points in the cell.
+    Centroid,
+    /// The first point in the cell.
+    First,
+}
+
+/// Downsample the points to one point per cell of the grid. The cells are in the order of their
+/// first point.
+/// # Example
+/// ```
+/// use multi_dim_point::voxel::{downsample, Representative, VoxelGrid};
+/// use multi_dim_point::Point;
+/// let points: Vec<Point<f64>> = vec![
+///     Point::new_from_vec(&vec![0.2, 0.2]),
+///     Point::new_from_vec(&vec![3.0, 3.0]),
+///     Point::new_from_vec(&vec![0.4, 0.6]),
+/// ];
+/// let grid = VoxelGrid::new(&Point::new_from_vec(&vec![0.0, 0.0]), 1.0);
+/// let centroids = downsample(&grid, &points, Representative::Centroid);
+/// assert!(centroids[0].close(&Point::new_from_vec(&vec![0.3, 0.4]), 1e-12));
+/// assert_eq!(centroids[1], points[1]);
+/// ```
+/// # Panic
+/// This function will panic if the dimensions of a point and the grid are not equal, or if a
+/// point has no cell (see [`VoxelGrid::cell_of`]).
+pub fn downsample(
+    grid: &VoxelGrid,
+    points: &[Point<f64>],
+    representative: Representative,
+) -> Vec<Point<f64>> {
+    // for each cell: its index in the output, the sum of its points and their count.
+    let mut cells: HashMap<Point<i64>, usize> = HashMap::new();
+    let mut sums: Vec<(Vec<f64>, usize)> = Vec::new();
+    for p in points {
+        let index = *cells.entry(grid.cell_of(p)).or_insert_with(|| {
+            sums.push((vec![0.0; p.get_size()], 0));
+            sums.len() - 1
+        });
+        let (sum, count) = &mut sums[index];
+        match representative {
+            Representative::Centroid => {
+                for (s, v) in sum.iter_mut().zip(p.get_vector()) {
+                    *s += v;
+                }
+            }
+            Representative::First => {
+                if *count == 0 {
+                    sum.copy_from_slice(p.get_vector());
+                }
+            }
+        }
+        *count += 1;
+    }
+    sums.into_iter()
+        .map(|(sum, count)| match representative {
+            Representative::Centroid => {
+                Point::new_from_vec(&sum.iter().map(|s| s / count as f64).collect())
+            }
+            Representative::First => Point::new_from_vec(&sum),
+        })
+        .collect()
+}